@@ -1,56 +1,239 @@
-/// Player abilities with cooldowns
+use crate::combat::DamageType;
+
+/// Most abilities a shade can hold: one for each of the keys 1–9
+pub const MAX_ABILITIES: usize = 9;
+
+/// What an ability needs from the player before it can fire
 #[derive(Clone, Copy, PartialEq, Eq)]
-pub enum Ability {
-    /// Dash: move 2 tiles in a direction, passing through enemies
-    Dash,
-    /// Spectral Scream: damage all visible enemies in radius 3
-    SpectralScream,
-    /// Lethe's Touch: heal 15 HP (long cooldown)
-    LethesTouch,
+pub enum AbilityTarget {
+    /// Fires at once, centred on the player
+    Instant,
+    /// Needs an enemy within this many tiles, chosen with the targeting cursor
+    Enemy(i32),
+    /// Needs a direction, or a tile as far away as the effect reaches
+    Direction,
 }
 
-impl Ability {
-    pub fn name(self) -> &'static str {
-        match self {
-            Ability::Dash => "Dash",
-            Ability::SpectralScream => "Spectral Scream",
-            Ability::LethesTouch => "Lethe's Touch",
-        }
-    }
+/// What an ability does when it fires
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Effect {
+    /// Move up to `distance` tiles, phasing through enemies and hurting those passed
+    Dash { distance: i32, trail: i32 },
+    /// Damage every visible enemy within `radius`, and maybe send them fleeing
+    Burst { radius: i32, damage: i32, damage_type: DamageType, fear: i32 },
+    /// Restore HP, and maybe shake off blindness, poison and stripped armour
+    Heal { amount: i32, cleanse: bool },
+    /// The target fights for you for a while
+    Possess { turns: i32 },
+    /// Walk through walls for a while
+    Phase { turns: i32 },
+    /// Every visible enemy within `radius` is rooted in place
+    Bind { radius: i32, turns: i32 },
+    /// Half the damage you take is passed along to the target
+    Tether { turns: i32 },
+}
 
-    pub fn key(self) -> char {
+impl Effect {
+    /// How far a directional effect reaches
+    pub fn reach(self) -> i32 {
         match self {
-            Ability::Dash => '1',
-            Ability::SpectralScream => '2',
-            Ability::LethesTouch => '3',
+            Effect::Dash { distance, .. } => distance,
+            _ => 1,
         }
     }
 
-    pub fn max_cooldown(self) -> i32 {
+    /// The numbers behind the effect, for the abilities screen
+    pub fn summary(self) -> String {
         match self {
-            Ability::Dash => 8,
-            Ability::SpectralScream => 12,
-            Ability::LethesTouch => 25,
+            Effect::Dash { distance, trail: 0 } => format!("dash {} tiles", distance),
+            Effect::Dash { distance, trail } => format!("dash {} tiles, {} damage to each enemy passed", distance, trail),
+            Effect::Burst { radius, damage, damage_type, fear: 0 } => {
+                format!("{} {} damage within {}", damage, damage_type.name().to_lowercase(), radius)
+            }
+            Effect::Burst { radius, damage, damage_type, fear } => format!(
+                "{} {} damage within {}, survivors flee for {} turns",
+                damage, damage_type.name().to_lowercase(), radius, fear,
+            ),
+            Effect::Heal { amount, cleanse: false } => format!("heal {} HP", amount),
+            Effect::Heal { amount, cleanse: true } => format!("heal {} HP, cleanse blindness, poison and stripped armour", amount),
+            Effect::Possess { turns } => format!("possess for {} turns", turns),
+            Effect::Phase { turns } => format!("phase for {} turns", turns),
+            Effect::Bind { radius, turns } => format!("root enemies within {} for {} turns", radius, turns),
+            Effect::Tether { turns } => format!("tether for {} turns", turns),
         }
     }
+}
 
-    pub fn description(self) -> &'static str {
-        match self {
-            Ability::Dash => "Move 2 tiles, phase through enemies",
-            Ability::SpectralScream => "Damage all nearby visible enemies",
-            Ability::LethesTouch => "Remember nothing. Heal 15 HP",
-        }
+/// An ability as data: everything the game needs to show it and fire it.
+/// `ranks` holds the effect at each rank, starting from rank I.
+#[derive(PartialEq, Eq)]
+pub struct AbilityDef {
+    pub name: &'static str,
+    pub description: &'static str,
+    /// Opens the log line when it fires; what it did follows
+    pub flavor: &'static str,
+    pub cooldown: i32,
+    pub hp_cost: i32,
+    pub target: AbilityTarget,
+    pub ranks: &'static [Effect],
+}
+
+pub static DASH: AbilityDef = AbilityDef {
+    name: "Dash",
+    description: "Move 2 tiles, phase through enemies",
+    flavor: "You dash through the grey air.",
+    cooldown: 8,
+    hp_cost: 0,
+    target: AbilityTarget::Direction,
+    ranks: &[
+        Effect::Dash { distance: 2, trail: 0 },
+        Effect::Dash { distance: 2, trail: 4 },
+        Effect::Dash { distance: 3, trail: 6 },
+    ],
+};
+
+pub static SPECTRAL_SCREAM: AbilityDef = AbilityDef {
+    name: "Spectral Scream",
+    description: "Damage all nearby visible enemies",
+    flavor: "You scream into the void.",
+    cooldown: 12,
+    hp_cost: 0,
+    target: AbilityTarget::Instant,
+    ranks: &[
+        Effect::Burst { radius: 3, damage: 6, damage_type: DamageType::Spectral, fear: 0 },
+        Effect::Burst { radius: 4, damage: 7, damage_type: DamageType::Spectral, fear: 3 },
+        Effect::Burst { radius: 5, damage: 9, damage_type: DamageType::Spectral, fear: 5 },
+    ],
+};
+
+pub static LETHES_TOUCH: AbilityDef = AbilityDef {
+    name: "Lethe's Touch",
+    description: "Remember nothing. Heal 15 HP",
+    flavor: "You touch the waters of Lethe. You forget your pain.",
+    cooldown: 25,
+    hp_cost: 0,
+    target: AbilityTarget::Instant,
+    ranks: &[
+        Effect::Heal { amount: 15, cleanse: false },
+        Effect::Heal { amount: 15, cleanse: true },
+        Effect::Heal { amount: 22, cleanse: true },
+    ],
+};
+
+pub static POSSESS: AbilityDef = AbilityDef {
+    name: "Possess",
+    description: "An enemy fights for you for 8 turns",
+    flavor: "You pour yourself out of your own edges.",
+    cooldown: 30,
+    hp_cost: 4,
+    target: AbilityTarget::Enemy(4),
+    ranks: &[Effect::Possess { turns: 8 }, Effect::Possess { turns: 12 }],
+};
+
+pub static PHASE: AbilityDef = AbilityDef {
+    name: "Phase Through Walls",
+    description: "Walk through stone for 6 turns",
+    flavor: "You thin until the walls forget you. Stone parts like mist.",
+    cooldown: 20,
+    hp_cost: 0,
+    target: AbilityTarget::Instant,
+    ranks: &[Effect::Phase { turns: 6 }, Effect::Phase { turns: 10 }],
+};
+
+pub static WAIL_OF_BINDING: AbilityDef = AbilityDef {
+    name: "Wail of Binding",
+    description: "Root nearby visible enemies for 4 turns",
+    flavor: "You wail.",
+    cooldown: 16,
+    hp_cost: 0,
+    target: AbilityTarget::Instant,
+    ranks: &[Effect::Bind { radius: 4, turns: 4 }, Effect::Bind { radius: 5, turns: 6 }],
+};
+
+pub static SOUL_TETHER: AbilityDef = AbilityDef {
+    name: "Soul Tether",
+    description: "For 8 turns, an enemy shares half your wounds",
+    flavor: "A grey cord runs from your chest.",
+    cooldown: 24,
+    hp_cost: 3,
+    target: AbilityTarget::Enemy(5),
+    ranks: &[Effect::Tether { turns: 8 }, Effect::Tether { turns: 12 }],
+};
+
+pub static SCYTHE_SWEEP: AbilityDef = AbilityDef {
+    name: "Scythe Sweep",
+    description: "Cut down every adjacent enemy",
+    flavor: "Your scythe sweeps round in a wide arc.",
+    cooldown: 8,
+    hp_cost: 0,
+    target: AbilityTarget::Instant,
+    ranks: &[
+        Effect::Burst { radius: 1, damage: 8, damage_type: DamageType::Physical, fear: 0 },
+        Effect::Burst { radius: 1, damage: 11, damage_type: DamageType::Physical, fear: 0 },
+        Effect::Burst { radius: 2, damage: 12, damage_type: DamageType::Physical, fear: 2 },
+    ],
+};
+
+pub static CHARGE: AbilityDef = AbilityDef {
+    name: "Charge",
+    description: "Run 2 tiles through the enemy line, hurting all in the way",
+    flavor: "You lower your head and charge.",
+    cooldown: 10,
+    hp_cost: 0,
+    target: AbilityTarget::Direction,
+    ranks: &[
+        Effect::Dash { distance: 2, trail: 4 },
+        Effect::Dash { distance: 3, trail: 6 },
+        Effect::Dash { distance: 3, trail: 9 },
+    ],
+};
+
+/// What every shade starts with
+pub static STARTING: [&AbilityDef; 3] = [&DASH, &SPECTRAL_SCREAM, &LETHES_TOUCH];
+
+/// Abilities that can be learned during a run
+pub static POOL: [&AbilityDef; 4] = [&POSSESS, &PHASE, &WAIL_OF_BINDING, &SOUL_TETHER];
+
+/// The key that fires the ability in this slot
+pub fn key_for(slot: usize) -> char {
+    char::from_digit(slot as u32 + 1, 10).unwrap_or('?')
+}
+
+/// Ranks are shown in Roman numerals
+pub fn rank_numeral(rank: usize) -> &'static str {
+    match rank {
+        1 => "I",
+        2 => "II",
+        3 => "III",
+        4 => "IV",
+        _ => "V",
     }
 }
 
+/// Ichor it takes to raise an ability from `rank` to the next
+pub fn upgrade_cost(rank: usize) -> u32 {
+    rank as u32 * 2
+}
+
 pub struct AbilityState {
-    pub ability: Ability,
+    pub def: &'static AbilityDef,
     pub cooldown: i32, // 0 = ready, >0 = turns until ready
+    pub rank: usize,   // starts at 1
 }
 
 impl AbilityState {
-    pub fn new(ability: Ability) -> Self {
-        AbilityState { ability, cooldown: 0 }
+    pub fn new(def: &'static AbilityDef) -> Self {
+        AbilityState { def, cooldown: 0, rank: 1 }
+    }
+
+    /// What the ability does at its current rank
+    pub fn effect(&self) -> Effect {
+        self.def.ranks[self.rank - 1]
+    }
+
+    /// The next rank's effect, if there is one
+    pub fn next_effect(&self) -> Option<Effect> {
+        self.def.ranks.get(self.rank).copied()
     }
 
     pub fn ready(&self) -> bool {
@@ -58,7 +241,7 @@ impl AbilityState {
     }
 
     pub fn trigger(&mut self) {
-        self.cooldown = self.ability.max_cooldown();
+        self.cooldown = self.def.cooldown;
     }
 
     pub fn tick(&mut self) {
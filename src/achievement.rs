@@ -0,0 +1,72 @@
+/// Turns an achievement pop-up stays on screen
+pub const POPUP_TURNS: i32 = 8;
+
+/// Feats remembered in the profile across runs
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Achievement {
+    Abstinent,
+    Banshee,
+    Pauper,
+    Unseen,
+    KindlyOnes,
+    Favoured,
+    Hellfire,
+    DailyBread,
+}
+
+impl Achievement {
+    pub const ALL: [Achievement; 8] = [
+        Achievement::Abstinent,
+        Achievement::Banshee,
+        Achievement::Pauper,
+        Achievement::Unseen,
+        Achievement::KindlyOnes,
+        Achievement::Favoured,
+        Achievement::Hellfire,
+        Achievement::DailyBread,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Achievement::Abstinent => "Abstinent",
+            Achievement::Banshee => "Banshee",
+            Achievement::Pauper => "Pauper's Crossing",
+            Achievement::Unseen => "Nobody Saw",
+            Achievement::KindlyOnes => "The Kindly Ones",
+            Achievement::Favoured => "Favoured",
+            Achievement::Hellfire => "Into the Fire",
+            Achievement::DailyBread => "Daily Bread",
+        }
+    }
+
+    pub fn description(self) -> &'static str {
+        match self {
+            Achievement::Abstinent => "Reach Elysium without picking up any nectar",
+            Achievement::Banshee => "Kill an Empusa with Spectral Scream",
+            Achievement::Pauper => "Reach Elysium with 0 obols",
+            Achievement::Unseen => "Descend without any monster seeing you on the floor",
+            Achievement::KindlyOnes => "Slay an Erinys",
+            Achievement::Favoured => "Take 5 boons in one run",
+            Achievement::Hellfire => "Reach Elysium with 5 or more heat",
+            Achievement::DailyBread => "Reach Elysium in a daily challenge",
+        }
+    }
+
+    /// Name in the profile file
+    pub fn key(self) -> &'static str {
+        match self {
+            Achievement::Abstinent => "abstinent",
+            Achievement::Banshee => "banshee",
+            Achievement::Pauper => "pauper",
+            Achievement::Unseen => "unseen",
+            Achievement::KindlyOnes => "kindly_ones",
+            Achievement::Favoured => "favoured",
+            Achievement::Hellfire => "hellfire",
+            Achievement::DailyBread => "daily_bread",
+        }
+    }
+
+    pub fn from_key(key: &str) -> Option<Achievement> {
+        Achievement::ALL.iter().copied().find(|a| a.key() == key)
+    }
+}
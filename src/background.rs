@@ -0,0 +1,200 @@
+use crate::ability::{self, AbilityDef};
+use crate::entity::Entity;
+use crate::profile::{Profile, Unlock};
+
+/// Memory fragments in each life: one lies on each of the first floors
+pub const FRAGMENT_COUNT: usize = 5;
+
+/// Who the shade was in life. Chosen before each run.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Background {
+    Farmer,
+    Soldier,
+    Priestess,
+    Thief,
+}
+
+impl Background {
+    pub const ALL: [Background; 4] = [
+        Background::Farmer,
+        Background::Soldier,
+        Background::Priestess,
+        Background::Thief,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Background::Farmer => "Farmer",
+            Background::Soldier => "Soldier",
+            Background::Priestess => "Priestess",
+            Background::Thief => "Thief",
+        }
+    }
+
+    /// Name in the profile file
+    pub fn key(self) -> &'static str {
+        match self {
+            Background::Farmer => "farmer",
+            Background::Soldier => "soldier",
+            Background::Priestess => "priestess",
+            Background::Thief => "thief",
+        }
+    }
+
+    pub fn from_key(key: &str) -> Option<Background> {
+        Background::ALL.iter().copied().find(|b| b.key() == key)
+    }
+
+    /// A line about the life, for the selection screen
+    pub fn description(self) -> &'static str {
+        match self {
+            Background::Farmer => "Barley, goats, forty summers. A strong back and no sword.",
+            Background::Soldier => "A spear in someone's war. You don't remember whose.",
+            Background::Priestess => "You kept Hecate's torches at the crossroads.",
+            Background::Thief => "Other people's purses. Other people's houses.",
+        }
+    }
+
+    /// What the life left behind in the shade's numbers
+    pub fn stats_text(self) -> &'static str {
+        match self {
+            Background::Farmer => "+10 max HP, -1 attack",
+            Background::Soldier => "+2 attack, +1 defense, -5 evasion",
+            Background::Priestess => "-5 max HP, +10 accuracy, +5 evasion",
+            Background::Thief => "-5 max HP, -1 defense, +15 evasion",
+        }
+    }
+
+    pub fn apply(self, player: &mut Entity) {
+        match self {
+            Background::Farmer => {
+                player.max_hp += 10;
+                player.attack -= 1;
+            }
+            Background::Soldier => {
+                player.attack += 2;
+                player.defense += 1;
+                player.evasion -= 5;
+            }
+            Background::Priestess => {
+                player.max_hp -= 5;
+                player.accuracy += 10;
+                player.evasion += 5;
+            }
+            Background::Thief => {
+                player.max_hp -= 5;
+                player.defense -= 1;
+                player.evasion += 15;
+            }
+        }
+        player.hp = player.max_hp;
+    }
+
+    /// The starting ability slot the life takes over, and what it puts there
+    pub fn ability_swap(self) -> (usize, &'static AbilityDef) {
+        match self {
+            Background::Farmer => (1, &ability::SCYTHE_SWEEP),
+            Background::Soldier => (0, &ability::CHARGE),
+            Background::Priestess => (1, &ability::WAIL_OF_BINDING),
+            Background::Thief => (1, &ability::PHASE),
+        }
+    }
+
+    /// Bought at the Pool of Mnemosyne before it can be chosen, if it must be
+    pub fn unlock(self) -> Option<Unlock> {
+        match self {
+            Background::Farmer | Background::Soldier => None,
+            Background::Priestess => Some(Unlock::Priestess),
+            Background::Thief => Some(Unlock::Thief),
+        }
+    }
+
+    /// Whether this life can be chosen yet
+    pub fn available(self, profile: &Profile) -> bool {
+        self.unlock().is_none_or(|u| profile.rank(u) > 0)
+    }
+
+    /// The first thing the shade remembers
+    pub fn intro(self) -> &'static str {
+        match self {
+            Background::Farmer => "Your hands remember a scythe, even if you don't.",
+            Background::Soldier => "You stand like someone still waiting for an order.",
+            Background::Priestess => "Three torches burn somewhere behind your eyes.",
+            Background::Thief => "You check your belt for a purse. Old habit.",
+        }
+    }
+
+    /// Scraps of the life, surfacing now and then
+    pub fn memories(self) -> &'static [&'static str] {
+        match self {
+            Background::Farmer => &[
+                "You remember the smell of rain on turned earth.",
+                "A goat with one horn. You named it. You can't remember what.",
+                "Your back aches. It always ached. Some things follow you down.",
+                "These flowers would never grow in good soil.",
+            ],
+            Background::Soldier => &[
+                "A shield wall, a held breath, a horn. Then nothing.",
+                "You count the exits of every room. You always did.",
+                "Someone owed you three months' pay.",
+                "You remember a friend's face, but not his name.",
+            ],
+            Background::Priestess => &[
+                "You remember the dogs howling at the crossroads before she came.",
+                "Honey, milk, blood. The offerings come back to you in order.",
+                "You taught the rites to girls who are old women now.",
+                "Hecate's torches look different from this side.",
+            ],
+            Background::Thief => &[
+                "Soft steps. You never lost the habit.",
+                "A window, a ledge, a dog that didn't bark. Good nights.",
+                "You remember a coin you didn't steal. Why didn't you?",
+                "Every lock down here has already been opened.",
+            ],
+        }
+    }
+
+    /// The life told in pieces, in the order they are found. The last is the name.
+    pub fn fragments(self) -> [&'static str; FRAGMENT_COUNT] {
+        match self {
+            Background::Farmer => [
+                "A terraced hillside above Tegea. The barley came in thin.",
+                "Your wife sang while she milked. Badly. You loved it anyway.",
+                "A fever in the wet spring. Your son sat up with you all night.",
+                "They put a coin on your tongue. Copper, all they had.",
+                "Your name was Ktesias. You say it out loud, and it fits.",
+            ],
+            Background::Soldier => [
+                "You marched north with the levy from Argos, nineteen years old.",
+                "A river crossing at dawn. Arrows out of the reeds.",
+                "You carried a wounded friend for two days. Only one of you lived.",
+                "Nobody put a coin under your tongue. Nobody found you at all.",
+                "Your name was Lykos. You say it out loud, and it fits.",
+            ],
+            Background::Priestess => [
+                "A white dog at the crossroads, and a girl sent to feed it.",
+                "The torches were yours to light at every dark of the moon.",
+                "A woman came to you to curse her husband. You helped her.",
+                "You died old, in the temple, with the dogs howling outside.",
+                "Your name was Theano. You say it out loud, and it fits.",
+            ],
+            Background::Thief => [
+                "A harbour town. Ships' ropes, fish scales, loose purses.",
+                "You stole a bronze mirror from a priest and never sold it.",
+                "A roof tile cracked under your foot. The drop was long enough.",
+                "Your sister paid for your obol. She never asked about the mirror.",
+                "Your name was Phormion. You say it out loud, and it fits.",
+            ],
+        }
+    }
+
+    /// The end screen's closing words, for a shade that found every fragment
+    pub fn remembered_ending(self) -> [&'static str; 2] {
+        match self {
+            Background::Farmer => ["You were Ktesias, of Tegea.", "You kept goats and barley."],
+            Background::Soldier => ["You were Lykos, of Argos.", "You held the line at the river."],
+            Background::Priestess => ["You were Theano, torch-keeper.", "Hecate's dogs knew your step."],
+            Background::Thief => ["You were Phormion, of the harbour.", "The mirror is still yours."],
+        }
+    }
+}
@@ -0,0 +1,23 @@
+/// What Charon charges to carry a shade across to Elysium
+pub const CHARON_FARE: u32 = 5;
+
+/// The floor of the Styx bank, walked by shades who couldn't pay
+pub const STYX_DEPTH: u32 = 8;
+
+/// How a run that didn't end in death ended
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Ending {
+    /// Paid the fare and was ferried across
+    Elysium,
+    /// Walked the bank of the Styx and found another way over
+    StyxBank,
+    /// Sat down on the shore to wait. Still waiting.
+    Shore,
+}
+
+impl Ending {
+    /// Whether the shade got across. Waiting on the shore isn't a win.
+    pub fn is_victory(self) -> bool {
+        self != Ending::Shore
+    }
+}
@@ -0,0 +1,125 @@
+use rand::Rng;
+
+/// Every attack that connects has this chance (out of 100) to be a critical
+const CRIT_CHANCE: i32 = 5;
+/// Rolls just above the hit chance graze instead of missing outright
+const GLANCE_BAND: i32 = 15;
+/// Rolls this close to 100 always miss, however good the odds
+const MISS_BAND: i32 = 5;
+
+/// What kind of harm a blow does; creatures resist some and fear others
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum DamageType {
+    Physical,
+    /// The stuff of shades: screams, Stygian iron
+    Spectral,
+    /// Flame from the Phlegethon
+    Fire,
+    /// Life stolen; the attacker heals half of what it takes
+    Drain,
+}
+
+impl DamageType {
+    pub fn name(self) -> &'static str {
+        match self {
+            DamageType::Physical => "physical",
+            DamageType::Spectral => "spectral",
+            DamageType::Fire => "fire",
+            DamageType::Drain => "drain",
+        }
+    }
+}
+
+/// How an attack roll landed
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum HitResult {
+    Miss,
+    Glancing,
+    Hit,
+    Critical,
+}
+
+/// Percent chance an attacker with `accuracy` lands a blow on a defender with `evasion`
+pub fn hit_chance(accuracy: i32, evasion: i32) -> i32 {
+    (accuracy - evasion).clamp(5, 95)
+}
+
+/// Percent chance the blow connects at all, glances included: the odds shown to the player
+pub fn connect_chance(accuracy: i32, evasion: i32) -> i32 {
+    let chance = hit_chance(accuracy, evasion);
+    (chance + GLANCE_BAND).min(100 - MISS_BAND).max(chance)
+}
+
+pub fn roll_attack(rng: &mut impl Rng, accuracy: i32, evasion: i32) -> HitResult {
+    let chance = hit_chance(accuracy, evasion);
+    let roll = rng.gen_range(0..100);
+    if roll < CRIT_CHANCE.min(chance) {
+        HitResult::Critical
+    } else if roll < chance {
+        HitResult::Hit
+    } else if roll < connect_chance(accuracy, evasion) {
+        HitResult::Glancing
+    } else {
+        HitResult::Miss
+    }
+}
+
+/// A glance that does no harm after all is logged and treated as a miss
+pub fn settle(result: HitResult, damage: i32) -> HitResult {
+    if result == HitResult::Glancing && damage <= 0 { HitResult::Miss } else { result }
+}
+
+/// Damage dealt by an attack of the given result.
+/// Criticals punch through armour, so even weak attackers stay dangerous.
+pub fn roll_damage(rng: &mut impl Rng, result: HitResult, atk: i32, def: i32) -> i32 {
+    let base = (atk - def + rng.gen_range(-1..=2)).max(0);
+    match result {
+        HitResult::Miss => 0,
+        HitResult::Glancing => base / 2,
+        HitResult::Hit => base,
+        HitResult::Critical => (atk * 3 / 2 + rng.gen_range(0..=2)).max(1),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    /// How many of 10,000 rolls come up as `result`
+    fn tally(accuracy: i32, evasion: i32, result: HitResult) -> usize {
+        let mut rng = StdRng::seed_from_u64(7);
+        (0..10_000).filter(|_| roll_attack(&mut rng, accuracy, evasion) == result).count()
+    }
+
+    #[test]
+    fn chances_are_clamped() {
+        assert_eq!(hit_chance(100, 0), 95);
+        assert_eq!(hit_chance(0, 100), 5);
+        assert_eq!(connect_chance(50, 0), 65);
+        assert_eq!(connect_chance(100, 0), 95);
+        assert_eq!(connect_chance(0, 100), 20);
+    }
+
+    #[test]
+    fn the_best_odds_still_miss() {
+        let misses = tally(200, 0, HitResult::Miss);
+        assert!((300..700).contains(&misses), "{} misses", misses);
+    }
+
+    #[test]
+    fn the_worst_odds_only_crit_or_glance() {
+        assert_eq!(tally(0, 200, HitResult::Hit), 0);
+        let glances = tally(0, 200, HitResult::Glancing);
+        assert!((1_200..1_800).contains(&glances), "{} glances", glances);
+    }
+
+    #[test]
+    fn harmless_glances_settle_as_misses() {
+        assert!(settle(HitResult::Glancing, 0) == HitResult::Miss);
+        assert!(settle(HitResult::Glancing, 2) == HitResult::Glancing);
+        assert!(settle(HitResult::Hit, 0) == HitResult::Hit);
+        assert!(settle(HitResult::Miss, 0) == HitResult::Miss);
+    }
+}
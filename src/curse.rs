@@ -0,0 +1,61 @@
+use rand::Rng;
+
+/// A curse on a piece of gear or a consumable. Cursed gear cannot be removed
+/// once equipped; a cursed consumable lays its curse on whoever uses it. Either
+/// way the curse stays hidden until then (or until a Sibyl's Leaf names it).
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Curse {
+    /// HP cannot rise above half of max while equipped
+    Withering,
+    /// Every monster on the floor knows where you are
+    Beacon,
+    /// The gear's bonuses turn against you
+    Leaden,
+}
+
+impl Curse {
+    pub fn name(self) -> &'static str {
+        match self {
+            Curse::Withering => "withering",
+            Curse::Beacon => "beacon",
+            Curse::Leaden => "leaden",
+        }
+    }
+
+    pub fn description(self) -> &'static str {
+        match self {
+            Curse::Withering => "HP capped at half while worn",
+            Curse::Beacon => "Draws every monster on the floor",
+            Curse::Leaden => "Its bonuses become penalties",
+        }
+    }
+
+    /// What the curse does to whoever uses a cursed consumable
+    pub fn used_description(self) -> &'static str {
+        match self {
+            Curse::Withering => "HP capped at half for a while after use",
+            Curse::Beacon => "Draws every monster on the floor for a while after use",
+            Curse::Leaden => "Does nothing at all",
+        }
+    }
+
+    pub fn random(rng: &mut impl Rng) -> Self {
+        match rng.gen_range(0..3) {
+            0 => Curse::Withering,
+            1 => Curse::Beacon,
+            _ => Curse::Leaden,
+        }
+    }
+}
+
+/// Chance (out of 100) that gear or a consumable found on the floor is cursed
+pub const CURSE_CHANCE: u32 = 25;
+/// Turns a withering or beacon curse lingers after a cursed consumable is used
+pub const CURSE_TURNS: i32 = 20;
+
+/// A font of Lethe water. Drinking washes curses away, and a little memory with them.
+pub struct LetheFont {
+    pub x: i32,
+    pub y: i32,
+    pub used: bool,
+}
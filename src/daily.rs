@@ -0,0 +1,176 @@
+use std::fs;
+use std::io::{self, Write};
+use std::path::PathBuf;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use crate::ability::{self, AbilityDef, AbilityState};
+use crate::background::Background;
+use crate::date::Date;
+use crate::entity::EntityKind;
+use crate::game::Game;
+use crate::profile::data_dir;
+use crate::score;
+
+/// A twist on the day's run, the same for everyone who plays it
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Omen {
+    Pilgrim,
+    Frail,
+    Gifted,
+    EmptyHanded,
+    IchorRich,
+    Dim,
+}
+
+impl Omen {
+    pub const ALL: [Omen; 6] = [
+        Omen::Pilgrim,
+        Omen::Frail,
+        Omen::Gifted,
+        Omen::EmptyHanded,
+        Omen::IchorRich,
+        Omen::Dim,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Omen::Pilgrim => "Pilgrim",
+            Omen::Frail => "Frail",
+            Omen::Gifted => "Gifted",
+            Omen::EmptyHanded => "Empty-handed",
+            Omen::IchorRich => "Ichor-rich",
+            Omen::Dim => "Dim",
+        }
+    }
+
+    pub fn description(self) -> &'static str {
+        match self {
+            Omen::Pilgrim => "Start with 5 obols",
+            Omen::Frail => "-10 max HP",
+            Omen::Gifted => "Start with a fourth ability from the echoes' pool",
+            Omen::EmptyHanded => "Two nectar instead of pebbles",
+            Omen::IchorRich => "Start with 4 ichor",
+            Omen::Dim => "See 2 tiles less",
+        }
+    }
+
+    fn apply(self, game: &mut Game, rng: &mut StdRng) {
+        match self {
+            Omen::Pilgrim => game.obols += 5,
+            Omen::Frail => {
+                game.player.max_hp -= 10;
+                game.player.hp = game.player.max_hp;
+            }
+            Omen::Gifted => {
+                let unknown: Vec<&'static AbilityDef> = ability::POOL.iter().copied()
+                    .filter(|def| !game.abilities.iter().any(|ab| std::ptr::eq(ab.def, *def)))
+                    .collect();
+                if !unknown.is_empty() {
+                    game.abilities.push(AbilityState::new(unknown[rng.gen_range(0..unknown.len())]));
+                }
+            }
+            Omen::EmptyHanded => {
+                game.inventory.stacks.retain(|s| s.kind != EntityKind::Pebble);
+                game.inventory.add(EntityKind::Nectar);
+                game.inventory.add(EntityKind::Nectar);
+            }
+            Omen::IchorRich => game.ichor += 4,
+            Omen::Dim => game.fov_radius = (game.fov_radius - 2).max(2),
+        }
+    }
+}
+
+/// Today's challenge: a seed from the date, and a life and omens drawn from it
+pub struct Daily {
+    pub date: Date,
+    pub seed: u64,
+    pub background: Background,
+    pub omens: [Omen; 2],
+}
+
+impl Daily {
+    pub fn today() -> Self {
+        let date = Date::today();
+        let seed = date.year as u64 * 10_000 + date.month as u64 * 100 + date.day as u64;
+        let mut rng = StdRng::seed_from_u64(seed);
+        let background = Background::ALL[rng.gen_range(0..Background::ALL.len())];
+        let first = rng.gen_range(0..Omen::ALL.len());
+        let second = (first + rng.gen_range(1..Omen::ALL.len())) % Omen::ALL.len();
+        Daily { date, seed, background, omens: [Omen::ALL[first], Omen::ALL[second]] }
+    }
+
+    /// The omens by name, for the title screen
+    pub fn omens_text(&self) -> String {
+        self.omens.iter().map(|o| o.name()).collect::<Vec<_>>().join(", ")
+    }
+
+    /// Lay the day's omens on a freshly made run
+    pub fn apply(&self, game: &mut Game) {
+        let mut rng = StdRng::seed_from_u64(self.seed);
+        for omen in self.omens {
+            omen.apply(game, &mut rng);
+        }
+        game.daily = Some(self.date);
+    }
+}
+
+/// One day's attempt, as kept in the history file
+pub struct DailyResult {
+    pub date: String,
+    pub background: String,
+    pub score: u32,
+    pub depth: u32,
+    pub cause: String,
+}
+
+impl DailyResult {
+    fn from_line(line: &str) -> Option<Self> {
+        let mut fields = line.splitn(5, '\t');
+        Some(DailyResult {
+            date: fields.next()?.to_string(),
+            background: fields.next()?.to_string(),
+            score: fields.next()?.parse().ok()?,
+            depth: fields.next()?.parse().ok()?,
+            cause: fields.next()?.to_string(),
+        })
+    }
+}
+
+/// Every daily run played on this machine, oldest first
+pub struct DailyHistory {
+    pub results: Vec<DailyResult>,
+}
+
+impl DailyHistory {
+    fn path() -> PathBuf {
+        data_dir().join("daily.txt")
+    }
+
+    pub fn load() -> Self {
+        let results = fs::read_to_string(Self::path())
+            .map(|text| text.lines().filter_map(DailyResult::from_line).collect())
+            .unwrap_or_default();
+        DailyHistory { results }
+    }
+
+    /// Add a finished daily run, in memory and on disk
+    pub fn record(&mut self, game: &Game) -> io::Result<()> {
+        let Some(date) = game.daily else { return Ok(()) };
+        let result = DailyResult {
+            date: date.to_string(),
+            background: game.background.name().to_string(),
+            score: score::score(game),
+            depth: game.depth,
+            cause: score::cause(game),
+        };
+        fs::create_dir_all(data_dir())?;
+        let mut file = fs::OpenOptions::new().create(true).append(true).open(Self::path())?;
+        writeln!(
+            file,
+            "{}\t{}\t{}\t{}\t{}",
+            result.date, result.background, result.score, result.depth, result.cause,
+        )?;
+        self.results.push(result);
+        Ok(())
+    }
+}
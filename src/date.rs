@@ -0,0 +1,68 @@
+use std::fmt;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// A calendar day (UTC), worked out from the system clock
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct Date {
+    pub year: i32,
+    pub month: u32,
+    pub day: u32,
+}
+
+fn now_secs() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |d| d.as_secs())
+}
+
+/// The time of day (UTC) as HHMMSS, to tell apart things from the same day
+pub fn time_of_day() -> String {
+    let secs = now_secs() % 86_400;
+    format!("{:02}{:02}{:02}", secs / 3600, secs / 60 % 60, secs % 60)
+}
+
+impl Date {
+    pub fn today() -> Self {
+        Date::from_days((now_secs() / 86_400) as i64)
+    }
+
+    /// The civil date `days` after 1970-01-01 (Howard Hinnant's algorithm)
+    fn from_days(days: i64) -> Self {
+        let z = days + 719_468;
+        let era = z.div_euclid(146_097);
+        let doe = z.rem_euclid(146_097);
+        let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+        let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+        let mp = (5 * doy + 2) / 153;
+        let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+        let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+        let year = (yoe + era * 400) as i32 + i32::from(month <= 2);
+        Date { year, month, day }
+    }
+}
+
+impl fmt::Display for Date {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:04}-{:02}-{:02}", self.year, self.month, self.day)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ymd(days: i64) -> String {
+        Date::from_days(days).to_string()
+    }
+
+    #[test]
+    fn epoch() {
+        assert_eq!(ymd(0), "1970-01-01");
+        assert_eq!(ymd(-1), "1969-12-31");
+    }
+
+    #[test]
+    fn leap_days() {
+        assert_eq!(ymd(11_016), "2000-02-29");
+        assert_eq!(ymd(11_017), "2000-03-01");
+        assert_eq!(ymd(19_782), "2024-02-29");
+    }
+}
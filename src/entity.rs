@@ -1,4 +1,8 @@
 use ratatui::style::Color;
+use crate::combat::DamageType;
+use crate::curse::Curse;
+use crate::equipment::{GearStats, Slot, WeaponTrait};
+use crate::potion::PotionEffect;
 
 #[derive(Clone, Copy, PartialEq, Eq)]
 pub enum EntityKind {
@@ -8,11 +12,25 @@ pub enum EntityKind {
     Lampad,       // underworld nymph, moderate
     Eurynomos,    // flesh-eating daemon
     Empusa,       // shapeshifter, dangerous
+    Erinys,       // a Fury, sent after shades who ask for it
     // Items
     Nectar,       // heals
     Obol,         // score/currency
     Moly,         // temporary strength boost
     StygianBlade, // weapon upgrade
+    Pebble,       // throwable
+    Potion(PotionEffect), // unidentified until drunk
+    SibylLeaf,    // identifies a potion
+    Echo,         // teaches an ability when picked up
+    Ichor,        // spent to rank up abilities
+    Memory,       // a fragment of the shade's own life
+    // Gear
+    Spear,         // reaches two tiles
+    Bident,        // Hades' own: hits two targets
+    Sickle,        // Kronos' harvest: bleeds
+    LinenShroud,   // light armour
+    BronzeCuirass, // heavy armour
+    OwlCharm,      // trinket of Athena
 }
 
 impl EntityKind {
@@ -23,10 +41,23 @@ impl EntityKind {
             EntityKind::Lampad => 'l',
             EntityKind::Eurynomos => 'E',
             EntityKind::Empusa => 'M',
+            EntityKind::Erinys => 'F',
             EntityKind::Nectar => '!',
             EntityKind::Obol => '$',
             EntityKind::Moly => '?',
             EntityKind::StygianBlade => '/',
+            EntityKind::Pebble => '*',
+            EntityKind::Potion(_) => '¡',
+            EntityKind::SibylLeaf => '~',
+            EntityKind::Echo => '&',
+            EntityKind::Ichor => '♦',
+            EntityKind::Memory => '§',
+            EntityKind::Spear => '|',
+            EntityKind::Bident => 'ψ',
+            EntityKind::Sickle => ')',
+            EntityKind::LinenShroud => '[',
+            EntityKind::BronzeCuirass => '[',
+            EntityKind::OwlCharm => '"',
         }
     }
 
@@ -37,10 +68,23 @@ impl EntityKind {
             EntityKind::Lampad => Color::Magenta,
             EntityKind::Eurynomos => Color::Red,
             EntityKind::Empusa => Color::LightRed,
+            EntityKind::Erinys => Color::Rgb(190, 30, 50),
             EntityKind::Nectar => Color::Green,
             EntityKind::Obol => Color::Yellow,
             EntityKind::Moly => Color::Cyan,
             EntityKind::StygianBlade => Color::White,
+            EntityKind::Pebble => Color::Gray,
+            EntityKind::Potion(_) => Color::Rgb(120, 160, 200),
+            EntityKind::SibylLeaf => Color::Rgb(140, 190, 110),
+            EntityKind::Echo => Color::Rgb(170, 200, 230),
+            EntityKind::Ichor => Color::Rgb(230, 190, 60),
+            EntityKind::Memory => Color::Rgb(220, 180, 200),
+            EntityKind::Spear => Color::Rgb(180, 140, 90),
+            EntityKind::Bident => Color::Rgb(140, 120, 200),
+            EntityKind::Sickle => Color::Rgb(200, 200, 210),
+            EntityKind::LinenShroud => Color::Rgb(200, 195, 180),
+            EntityKind::BronzeCuirass => Color::Rgb(205, 127, 50),
+            EntityKind::OwlCharm => Color::Rgb(150, 170, 200),
         }
     }
 
@@ -51,21 +95,163 @@ impl EntityKind {
             EntityKind::Lampad => "Lampad",
             EntityKind::Eurynomos => "Eurynomos",
             EntityKind::Empusa => "Empusa",
+            EntityKind::Erinys => "Erinys",
             EntityKind::Nectar => "Nectar",
             EntityKind::Obol => "Obol",
             EntityKind::Moly => "Moly",
             EntityKind::StygianBlade => "Stygian Blade",
+            EntityKind::Pebble => "River Pebble",
+            EntityKind::Potion(_) => "Potion",
+            EntityKind::SibylLeaf => "Sibyl's Leaf",
+            EntityKind::Echo => "Echo",
+            EntityKind::Ichor => "Ichor",
+            EntityKind::Memory => "Memory",
+            EntityKind::Spear => "Spear",
+            EntityKind::Bident => "Bident",
+            EntityKind::Sickle => "Sickle",
+            EntityKind::LinenShroud => "Linen Shroud",
+            EntityKind::BronzeCuirass => "Bronze Cuirass",
+            EntityKind::OwlCharm => "Owl Charm",
         }
     }
 
     pub fn is_enemy(self) -> bool {
         matches!(self, EntityKind::LostShade | EntityKind::Lampad
-            | EntityKind::Eurynomos | EntityKind::Empusa)
+            | EntityKind::Eurynomos | EntityKind::Empusa | EntityKind::Erinys)
     }
 
     pub fn is_item(self) -> bool {
         matches!(self, EntityKind::Nectar | EntityKind::Obol
-            | EntityKind::Moly | EntityKind::StygianBlade)
+            | EntityKind::Moly | EntityKind::StygianBlade | EntityKind::Pebble
+            | EntityKind::Potion(_) | EntityKind::SibylLeaf | EntityKind::Echo
+            | EntityKind::Ichor | EntityKind::Memory)
+            || self.equip_slot().is_some()
+    }
+
+    /// The slot this kind goes in, if it can be equipped
+    pub fn equip_slot(self) -> Option<Slot> {
+        match self {
+            EntityKind::StygianBlade | EntityKind::Spear
+            | EntityKind::Bident | EntityKind::Sickle => Some(Slot::Weapon),
+            EntityKind::LinenShroud | EntityKind::BronzeCuirass => Some(Slot::Armour),
+            EntityKind::OwlCharm => Some(Slot::Trinket),
+            _ => None,
+        }
+    }
+
+    /// Stat bonuses while equipped
+    pub fn gear_stats(self) -> GearStats {
+        let (attack, defense, accuracy, evasion) = match self {
+            EntityKind::StygianBlade => (2, 0, 0, 0),
+            EntityKind::Spear => (1, 0, 5, 0),
+            EntityKind::Bident => (1, 0, 0, 0),
+            EntityKind::Sickle => (1, 0, 0, 0),
+            EntityKind::LinenShroud => (0, 0, 0, 10),   // hard to pin down
+            EntityKind::BronzeCuirass => (0, 3, 0, -10), // hard to miss
+            EntityKind::OwlCharm => (0, 0, 10, 0),
+            _ => (0, 0, 0, 0),
+        };
+        GearStats { attack, defense, accuracy, evasion }
+    }
+
+    pub fn weapon_trait(self) -> WeaponTrait {
+        match self {
+            EntityKind::Spear => WeaponTrait::Reach,
+            EntityKind::Bident => WeaponTrait::Cleave,
+            EntityKind::Sickle => WeaponTrait::Bleed,
+            _ => WeaponTrait::None,
+        }
+    }
+
+    /// Damage type of blows struck with this weapon
+    pub fn weapon_damage_type(self) -> DamageType {
+        match self {
+            EntityKind::StygianBlade => DamageType::Spectral,
+            _ => DamageType::Physical,
+        }
+    }
+
+    /// Short effect text for the inventory screen
+    pub fn description(self) -> &'static str {
+        match self {
+            EntityKind::Nectar => "Heals 10 HP",
+            EntityKind::Obol => "Payment for the ferryman",
+            EntityKind::Moly => "+4 attack for 15 turns",
+            EntityKind::StygianBlade => "Weapon. +2 attack; blows cut spectral",
+            EntityKind::Pebble => "Smooth, heavy. Made for throwing",
+            EntityKind::Potion(effect) => effect.description(),
+            EntityKind::SibylLeaf => "Reveals the nature of one unknown potion",
+            EntityKind::Echo => "Teaches an ability when picked up",
+            EntityKind::Ichor => "Golden blood of the gods. Ranks up abilities",
+            EntityKind::Memory => "A piece of who you were",
+            EntityKind::Spear => "Weapon. +1 attack; reaches two tiles",
+            EntityKind::Bident => "Weapon. +1 attack; strikes two foes",
+            EntityKind::Sickle => "Weapon. +1 attack; wounds bleed",
+            EntityKind::LinenShroud => "Armour. +10 evasion",
+            EntityKind::BronzeCuirass => "Armour. +3 defense, -10 evasion",
+            EntityKind::OwlCharm => "Trinket. +10 accuracy",
+            _ => "",
+        }
+    }
+
+    /// Whether this can turn up cursed: gear, and anything used up from the pack
+    pub fn cursable(self) -> bool {
+        self.equip_slot().is_some()
+            || matches!(self, EntityKind::Nectar | EntityKind::Moly | EntityKind::Potion(_) | EntityKind::SibylLeaf)
+    }
+
+    /// Whether several of these share one inventory slot
+    pub fn stackable(self) -> bool {
+        matches!(self, EntityKind::Nectar | EntityKind::Moly | EntityKind::Pebble
+            | EntityKind::Potion(_) | EntityKind::SibylLeaf)
+    }
+
+    /// Base damage when thrown at something
+    pub fn throw_damage(self) -> i32 {
+        match self {
+            EntityKind::Pebble => 4,
+            EntityKind::StygianBlade => 6,
+            EntityKind::Spear => 7, // it was always a javelin at heart
+            EntityKind::Bident | EntityKind::Sickle => 4,
+            _ => 1,
+        }
+    }
+
+    /// Whether the item breaks when thrown instead of landing intact
+    pub fn fragile(self) -> bool {
+        matches!(self, EntityKind::Nectar | EntityKind::Moly | EntityKind::Potion(_))
+    }
+
+    /// Percent of incoming damage of type `dt` this kind actually takes
+    pub fn resistance(self, dt: DamageType) -> i32 {
+        match (self, dt) {
+            (EntityKind::LostShade, DamageType::Spectral) => 200, // barely holding together
+            (EntityKind::LostShade, DamageType::Drain) => 50,     // little life left to take
+            (EntityKind::Lampad, DamageType::Fire) => 0,          // Hecate's torch-bearers
+            (EntityKind::Eurynomos, DamageType::Spectral) => 25,  // it eats the dead
+            (EntityKind::Eurynomos, DamageType::Fire) => 150,
+            (EntityKind::Empusa, DamageType::Physical) => 75,     // never quite where it seems
+            (EntityKind::Empusa, DamageType::Drain) => 0,
+            (EntityKind::Erinys, DamageType::Spectral) => 50,     // she punishes shades for a living
+            _ => 100,
+        }
+    }
+
+    /// What kind of damage this creature's attacks deal
+    pub fn attack_type(self) -> DamageType {
+        match self {
+            EntityKind::Lampad => DamageType::Fire,
+            EntityKind::Empusa => DamageType::Drain,
+            _ => DamageType::Physical,
+        }
+    }
+
+    /// How far this kind can shoot, if it attacks at range at all
+    pub fn ranged_range(self) -> Option<i32> {
+        match self {
+            EntityKind::Lampad => Some(5),
+            _ => None,
+        }
     }
 }
 
@@ -77,8 +263,16 @@ pub struct Entity {
     pub max_hp: i32,
     pub attack: i32,
     pub defense: i32,
+    pub accuracy: i32, // base chance to hit, before the target's evasion
+    pub evasion: i32,
+    pub damage_type: DamageType, // what this entity's melee deals
     pub alive: bool,
     pub strength_turns: i32, // moly buff remaining turns
+    pub bleed_turns: i32,    // sickle wounds remaining
+    pub possessed_turns: i32, // fighting for the player
+    pub bound_turns: i32,     // rooted by a wail
+    pub fear_turns: i32,      // fleeing a scream
+    pub curse: Option<Curse>, // items only; hidden until equipped
 }
 
 impl Entity {
@@ -88,8 +282,15 @@ impl Entity {
             kind: EntityKind::Player,
             hp: 40, max_hp: 40,
             attack: 5, defense: 3,
+            accuracy: 80, evasion: 10,
+            damage_type: DamageType::Physical,
             alive: true,
             strength_turns: 0,
+            bleed_turns: 0,
+            possessed_turns: 0,
+            bound_turns: 0,
+            fear_turns: 0,
+            curse: None,
         }
     }
 
@@ -116,14 +317,34 @@ impl Entity {
                 (8.0 * scale) as i32,
                 (3.0 * scale) as i32,
             ),
+            EntityKind::Erinys => (
+                (35.0 * scale) as i32,
+                (7.0 * scale) as i32,
+                (2.0 * scale) as i32,
+            ),
             _ => (1, 0, 0),
         };
+        let (acc, eva) = match kind {
+            EntityKind::LostShade => (55, 5),  // confused, flails
+            EntityKind::Lampad => (70, 15),
+            EntityKind::Eurynomos => (75, 5),  // slow but relentless
+            EntityKind::Empusa => (80, 20),
+            EntityKind::Erinys => (85, 15), // she doesn't miss often
+            _ => (0, 0),
+        };
         Entity {
             x, y, kind,
             hp, max_hp: hp,
             attack: atk, defense: def,
+            accuracy: acc + depth as i32 * 2, evasion: eva,
+            damage_type: kind.attack_type(),
             alive: true,
             strength_turns: 0,
+            bleed_turns: 0,
+            possessed_turns: 0,
+            bound_turns: 0,
+            fear_turns: 0,
+            curse: None,
         }
     }
 
@@ -132,8 +353,15 @@ impl Entity {
             x, y, kind,
             hp: 0, max_hp: 0,
             attack: 0, defense: 0,
+            accuracy: 0, evasion: 0,
+            damage_type: DamageType::Physical,
             alive: true,
             strength_turns: 0,
+            bleed_turns: 0,
+            possessed_turns: 0,
+            bound_turns: 0,
+            fear_turns: 0,
+            curse: None,
         }
     }
 
@@ -0,0 +1,71 @@
+/// Where a piece of gear is worn
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Slot {
+    Weapon,
+    Armour,
+    Trinket,
+}
+
+impl Slot {
+    /// Verb used when putting something in this slot
+    pub fn verb(self) -> &'static str {
+        match self {
+            Slot::Weapon => "wield",
+            Slot::Armour => "wear",
+            Slot::Trinket => "fasten",
+        }
+    }
+
+    /// Marker shown next to equipped items in the pack
+    pub fn marker(self) -> &'static str {
+        match self {
+            Slot::Weapon => "(wielded)",
+            Slot::Armour => "(worn)",
+            Slot::Trinket => "(fastened)",
+        }
+    }
+}
+
+/// What sets a weapon apart beyond its numbers
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum WeaponTrait {
+    None,
+    /// Strikes an enemy two tiles away if the tile between is empty
+    Reach,
+    /// Also strikes one other adjacent enemy
+    Cleave,
+    /// Opens wounds that bleed for a few turns
+    Bleed,
+}
+
+/// Stat bonuses granted by a piece of gear while equipped
+#[derive(Clone, Copy, Default)]
+pub struct GearStats {
+    pub attack: i32,
+    pub defense: i32,
+    pub accuracy: i32,
+    pub evasion: i32,
+}
+
+impl GearStats {
+    pub fn plus(self, other: GearStats) -> GearStats {
+        GearStats {
+            attack: self.attack + other.attack,
+            defense: self.defense + other.defense,
+            accuracy: self.accuracy + other.accuracy,
+            evasion: self.evasion + other.evasion,
+        }
+    }
+
+    pub fn negated(self) -> GearStats {
+        GearStats {
+            attack: -self.attack,
+            defense: -self.defense,
+            accuracy: -self.accuracy,
+            evasion: -self.evasion,
+        }
+    }
+}
+
+/// Turns a fresh sickle wound keeps bleeding
+pub const BLEED_TURNS: i32 = 3;
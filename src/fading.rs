@@ -0,0 +1,87 @@
+/// Fading at which the shade is all but gone, and an Erinys comes to collect it
+pub const FADING_MAX: u32 = 100;
+/// Turns on a floor before the shade starts to fade
+pub const FADING_GRACE: u32 = 80;
+/// After the grace, fading rises one point every this many turns
+pub const FADING_INTERVAL: u32 = 4;
+/// Fading lost on each descent
+pub const DESCENT_RELIEF: u32 = 30;
+/// Fading lost when an echo is taken in
+pub const ECHO_RELIEF: u32 = 20;
+/// Fading lost when a fragment of the shade's own life comes back
+pub const MEMORY_RELIEF: u32 = 25;
+
+/// How far gone the shade is. Lingering on a floor wears it away.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Stage {
+    Whole,
+    Thinning,
+    Fraying,
+    Vanishing,
+}
+
+impl Stage {
+    pub fn from_fading(fading: u32) -> Stage {
+        match fading {
+            0..=24 => Stage::Whole,
+            25..=49 => Stage::Thinning,
+            50..=74 => Stage::Fraying,
+            _ => Stage::Vanishing,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Stage::Whole => "Whole",
+            Stage::Thinning => "Thinning",
+            Stage::Fraying => "Fraying",
+            Stage::Vanishing => "Vanishing",
+        }
+    }
+
+    /// Percent of max HP the shade can still hold
+    pub fn hp_percent(self) -> i32 {
+        match self {
+            Stage::Whole => 100,
+            Stage::Thinning => 90,
+            Stage::Fraying => 80,
+            Stage::Vanishing => 70,
+        }
+    }
+
+    /// Tiles of sight lost
+    pub fn sight_loss(self) -> i32 {
+        match self {
+            Stage::Whole | Stage::Thinning => 0,
+            Stage::Fraying => 2,
+            Stage::Vanishing => 3,
+        }
+    }
+
+    /// Logged when the shade fades into this stage
+    pub fn message(self) -> &'static str {
+        match self {
+            Stage::Whole => "You feel whole again.",
+            Stage::Thinning => "You've lingered too long. Your edges are thinning.",
+            Stage::Fraying => "You are fraying. The dark creeps closer to your eyes.",
+            Stage::Vanishing => "You are vanishing. Something with wings has noticed.",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stage_boundaries() {
+        assert!(Stage::from_fading(0) == Stage::Whole);
+        assert!(Stage::from_fading(24) == Stage::Whole);
+        assert!(Stage::from_fading(25) == Stage::Thinning);
+        assert!(Stage::from_fading(49) == Stage::Thinning);
+        assert!(Stage::from_fading(50) == Stage::Fraying);
+        assert!(Stage::from_fading(74) == Stage::Fraying);
+        assert!(Stage::from_fading(75) == Stage::Vanishing);
+        assert!(Stage::from_fading(FADING_MAX) == Stage::Vanishing);
+    }
+}
@@ -1,7 +1,8 @@
 use rand::Rng;
+use crate::background::Background;
 
 /// Ambient messages that play occasionally to build atmosphere
-pub fn ambient_message(depth: u32, turns: u32) -> Option<&'static str> {
+pub fn ambient_message(depth: u32, _turns: u32, background: Background) -> Option<&'static str> {
     let mut rng = rand::thread_rng();
 
     // Only trigger occasionally
@@ -9,6 +10,12 @@ pub fn ambient_message(depth: u32, turns: u32) -> Option<&'static str> {
         return None;
     }
 
+    // Sometimes it's the old life surfacing instead of the meadows
+    if rng.gen_ratio(1, 4) {
+        let memories = background.memories();
+        return Some(memories[rng.gen_range(0..memories.len())]);
+    }
+
     let pool: &[&str] = match depth {
         1 => &[
             "The grey flowers sway in a wind you can't feel.",
@@ -60,6 +67,13 @@ pub fn ambient_message(depth: u32, turns: u32) -> Option<&'static str> {
             "Almost there. Almost somewhere.",
             "The flowers thin. The walls retreat. Space opens.",
         ],
+        8 => &[
+            "The Styx laps at the bank. It is colder than water should be.",
+            "Faces drift beneath the surface. Some of them look up.",
+            "Across the river, a light. It does not get closer.",
+            "Somewhere behind you, an oar dips. Not for you.",
+            "The reeds hiss your name. You still don't remember it.",
+        ],
         _ => &[
             "The grey stretches on.",
         ],
@@ -78,6 +92,7 @@ pub fn depth_message(depth: u32) -> &'static str {
         5 => "The deep meadows. Few shades wander willingly here.",
         6 => "Near the bottom. The air trembles.",
         7 => "The final terrace. Beyond this: Elysium, or oblivion.",
+        8 => "The bank of the Styx. The long way round.",
         _ => "Deeper still.",
     }
 }
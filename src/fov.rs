@@ -42,3 +42,37 @@ pub fn compute_fov(map: &mut Map, px: i32, py: i32, radius: i32) {
         }
     }
 }
+
+/// Bresenham line from (x0, y0) to (x1, y1), excluding the start point
+pub fn line(x0: i32, y0: i32, x1: i32, y1: i32) -> Vec<(i32, i32)> {
+    let mut points = Vec::new();
+    let dx = (x1 - x0).abs();
+    let dy = -(y1 - y0).abs();
+    let sx = if x0 < x1 { 1 } else { -1 };
+    let sy = if y0 < y1 { 1 } else { -1 };
+    let mut err = dx + dy;
+    let (mut x, mut y) = (x0, y0);
+
+    while x != x1 || y != y1 {
+        let e2 = 2 * err;
+        if e2 >= dy {
+            err += dy;
+            x += sx;
+        }
+        if e2 <= dx {
+            err += dx;
+            y += sy;
+        }
+        points.push((x, y));
+    }
+    points
+}
+
+/// Whether a projectile can fly from one point to another without hitting a wall
+pub fn line_of_fire(map: &Map, x0: i32, y0: i32, x1: i32, y1: i32) -> bool {
+    let path = line(x0, y0, x1, y1);
+    let last = path.len().saturating_sub(1);
+    path.iter().take(last).all(|&(x, y)| {
+        map.in_bounds(x, y) && map.tiles[y as usize][x as usize].transparent()
+    })
+}
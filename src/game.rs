@@ -1,19 +1,47 @@
-use rand::Rng;
-use crate::ability::{Ability, AbilityState};
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+use crate::achievement::{Achievement, POPUP_TURNS};
+use crate::ability::{self, AbilityDef, AbilityState, AbilityTarget, Effect, MAX_ABILITIES};
+use crate::background::{Background, FRAGMENT_COUNT};
+use crate::charon::{Ending, CHARON_FARE, STYX_DEPTH};
+use crate::date::Date;
+use crate::curse::{Curse, LetheFont, CURSE_TURNS};
+use crate::combat::{connect_chance, roll_attack, roll_damage, settle, DamageType, HitResult};
 use crate::entity::{Entity, EntityKind};
+use crate::fading::{self, Stage, FADING_MAX};
 use crate::flavor;
-use crate::fov::compute_fov;
-use crate::map::Map;
-use crate::shrine::{Boon, Shrine};
+use crate::heat::{self, Heat, ERINYS_DELAY};
+use crate::fov::{compute_fov, line, line_of_fire};
+use crate::equipment::{Slot, WeaponTrait, BLEED_TURNS};
+use crate::inventory::{Inventory, ItemStack, INVENTORY_CAPACITY};
+use crate::level::{self, Growth};
+use crate::map::{Map, Tile, MAP_H, MAP_W};
+use crate::potion::{PotionBook, PotionEffect};
+use crate::profile::{Profile, Unlock};
+use crate::recap::{DamageEvent, RECAP_EVENTS, RECAP_TURNS};
+use crate::shop::{Shop, Ware};
+use crate::shrine::{Boon, Offering, Rarity, Shrine};
 use crate::spawn::populate_room;
+use crate::targeting::{TargetMode, Targeting};
 
-const LOG_SIZE: usize = 8;
+/// Messages kept: the log panel shows the newest that fit, the morgue file all of them
+const LOG_SIZE: usize = 50;
+/// Accuracy lost when attacking from range
+const RANGED_PENALTY: i32 = 10;
 
 pub enum GameState {
     Title,
+    Background, // choosing who the shade was
+    Heat,       // then what hardships to take on
+    Mnemosyne,
+    Achievements,
+    Codex,
+    Daily,
+    HighScores,
     Playing,
     Dead,
-    Victory,
+    Ended, // at the Styx: across, or still on the shore
 }
 
 pub enum Action {
@@ -23,7 +51,13 @@ pub enum Action {
     Descend,
     Interact,
     UseAbility(usize),
+    UseAbilityAt(usize, i32, i32),
     Look,
+    Throw(usize, i32, i32), // inventory slot, target tile
+    UseItem(usize),
+    DropItem(usize),
+    Equip(usize),
+    Unequip(usize),
 }
 
 pub struct Game {
@@ -31,57 +65,151 @@ pub struct Game {
     pub player: Entity,
     pub entities: Vec<Entity>,
     pub shrines: Vec<Shrine>,
+    pub boons: Vec<Boon>, // every boon taken this run, in order
     pub log: Vec<String>,
+    pub seed: u64, // decides the layout and population of every floor
+    pub floor_rng: StdRng, // the current floor's, carried on for its shrine and stall draws
+    pub background: Background,
+    pub daily: Option<Date>, // the day's challenge this run is playing, if any
+    pub heat: Vec<Heat>,     // hardships chosen before the run
+    pub floor_turns: u32,    // turns spent on the current floor
+    pub spotted: bool,       // a monster has laid eyes on the shade on this floor
+    pub fading: u32,         // how far the shade has worn away, up to FADING_MAX
+    pub achievements: Vec<Achievement>, // every one earned, in this run or before
+    pub popups: Vec<(Achievement, i32)>, // newly earned, with turns left on screen
     pub depth: u32,
     pub obols: u32,
+    pub ichor: u32, // spent on ability ranks
     pub turns: u32,
+    pub kills: Vec<(EntityKind, u32)>, // by kind, in the order first slain
+    pub found: Vec<(EntityKind, u32)>, // items picked up, by kind
+    pub fragments: usize,              // memory fragments found this run, always in order
+    pub level: u32,
+    pub xp: u32,                     // towards the next level
+    pub growth_offers: Vec<Growth>,  // a level-up's choices, open until one is taken
+    pub levels_pending: u32,         // level-ups still to be chosen, counting the open one
     pub game_over: bool,
-    pub victory: bool,
+    pub ending: Option<Ending>,
+    pub charon_prompt: bool, // standing before the ferryman
     pub killed_by: String,
     pub abilities: Vec<AbilityState>,
     pub fov_radius: i32,
     pub shrine_prompt: Option<usize>, // index into shrines vec
+    pub shops: Vec<Shop>,
+    pub fonts: Vec<LetheFont>,
+    pub shop_prompt: Option<usize>, // index into shops vec
     pub blind_turns: i32, // blinded by lampad
+    pub poison_turns: i32,
+    pub haste_turns: i32,
+    pub wither_turns: i32, // left by a cursed consumable
+    pub beacon_turns: i32, // likewise
+    pub potions: PotionBook,
+    pub inventory: Inventory,
+    pub inventory_cursor: Option<usize>, // Some while the inventory screen is open
+    pub ability_menu: bool,
+    pub targeting: Option<Targeting>,
+    pub known_resists: Vec<(EntityKind, DamageType)>, // learned by hitting things
+    pub damage_events: Vec<DamageEvent>, // last few blows taken, for the death recap
+    pub hp_history: Vec<i32>,            // HP at the end of each recent turn
+    pub defense_stripped: i32,           // defense lost to Eurynomos
+    pub phase_turns: i32,
+    pub phase_anchor: (i32, i32), // last open tile, in case phasing ends inside stone
+    pub tether: Option<(usize, i32)>, // (entity index, turns left)
+    pub decoy: Option<(i32, i32, i32)>, // Nyx's shadow: (x, y, turns left)
+    pub character_screen: bool,
 }
 
 impl Game {
-    pub fn new() -> Self {
+    pub fn new(profile: &Profile, seed: u64, background: Background, heat: Vec<Heat>) -> Self {
         let depth = 1;
-        let map = Map::new(depth);
+        let mut rng = floor_rng(seed, depth);
+        let map = Map::new(depth, &mut rng);
         let (px, py) = map.rooms[0].center();
-        let player = Entity::player(px as i32, py as i32);
+        let mut player = Entity::player(px as i32, py as i32);
+        player.max_hp += 5 * profile.rank(Unlock::Vigor) as i32;
+        player.attack += profile.rank(Unlock::Strength) as i32;
+        background.apply(&mut player);
 
         let mut entities = Vec::new();
         let mut shrines = Vec::new();
         for i in 1..map.rooms.len() {
             let room = map.rooms[i];
-            populate_room(&room, depth, &mut entities, &map);
+            populate_room(&room, &mut entities, &map, &mut rng);
         }
         // Place shrine in a random middle room (not first or last)
-        if map.rooms.len() > 3 {
+        if map.rooms.len() > 3 && !heat.contains(&Heat::Godless) {
             let shrine_room = &map.rooms[map.rooms.len() / 2];
             let (sx, sy) = shrine_room.center();
-            shrines.push(Shrine::new(sx as i32, sy as i32));
+            shrines.push(Shrine::new(sx as i32, sy as i32, &[], &mut rng));
         }
 
-        let abilities = vec![
-            AbilityState::new(Ability::Dash),
-            AbilityState::new(Ability::SpectralScream),
-            AbilityState::new(Ability::LethesTouch),
-        ];
+        let mut abilities: Vec<AbilityState> = ability::STARTING.iter().map(|&def| AbilityState::new(def)).collect();
+        let (slot, def) = background.ability_swap();
+        abilities[slot] = AbilityState::new(def);
+        if profile.rank(Unlock::FourthGift) > 0 {
+            let unknown: Vec<&'static AbilityDef> = ability::POOL.iter().copied()
+                .filter(|def| !abilities.iter().any(|ab| std::ptr::eq(ab.def, *def)))
+                .collect();
+            abilities.push(AbilityState::new(unknown[rand::thread_rng().gen_range(0..unknown.len())]));
+        }
 
         let mut game = Game {
-            map, player, entities, shrines,
+            map, player, entities, shrines, seed, background,
+            floor_rng: rng,
+            daily: None,
+            heat,
+            floor_turns: 0,
+            spotted: false,
+            fading: 0,
+            achievements: Vec::new(),
+            popups: Vec::new(),
+            boons: Vec::new(),
             log: Vec::new(),
-            depth, obols: 0, turns: 0,
-            game_over: false, victory: false,
+            depth, obols: 0, ichor: 0, turns: 0,
+            kills: Vec::new(),
+            found: Vec::new(),
+            fragments: 0,
+            level: 1,
+            xp: 0,
+            growth_offers: Vec::new(),
+            levels_pending: 0,
+            game_over: false,
+            ending: None,
+            charon_prompt: false,
             killed_by: String::new(),
             abilities,
             fov_radius: 8,
             shrine_prompt: None,
+            shops: Vec::new(),
+            fonts: Vec::new(),
+            shop_prompt: None,
             blind_turns: 0,
+            poison_turns: 0,
+            haste_turns: 0,
+            wither_turns: 0,
+            beacon_turns: 0,
+            potions: PotionBook::new(&mut StdRng::seed_from_u64(seed)),
+            inventory: Inventory::new(INVENTORY_CAPACITY),
+            inventory_cursor: None,
+            ability_menu: false,
+            targeting: None,
+            known_resists: Vec::new(),
+            damage_events: Vec::new(),
+            hp_history: Vec::new(),
+            defense_stripped: 0,
+            phase_turns: 0,
+            phase_anchor: (px as i32, py as i32),
+            tether: None,
+            decoy: None,
+            character_screen: false,
         };
-        compute_fov(&mut game.map, game.player.x, game.player.y, game.fov_radius);
+        for _ in 0..3 {
+            game.inventory.add(EntityKind::Pebble);
+        }
+        game.stubborn_dead();
+        game.place_fragment();
+        let fov = game.effective_fov();
+        compute_fov(&mut game.map, game.player.x, game.player.y, fov);
         game
     }
 
@@ -92,12 +220,137 @@ impl Game {
         }
     }
 
+    pub fn kill_count(&self) -> u32 {
+        self.kills.iter().map(|(_, n)| n).sum()
+    }
+
     pub fn is_over(&self) -> bool {
         self.game_over
     }
 
     pub fn effective_fov(&self) -> i32 {
-        if self.blind_turns > 0 { 3 } else { self.fov_radius }
+        // Never so dim that the shade loses sight of its own tile
+        let fov = (self.fov_radius - self.fading_stage().sight_loss()).max(1);
+        if self.blind_turns > 0 || self.has_heat(Heat::Lightless) { fov.min(3) } else { fov }
+    }
+
+    pub fn fading_stage(&self) -> Stage {
+        Stage::from_fading(self.fading)
+    }
+
+    /// Lingering wears the shade away. At the very end an Erinys comes for what's left.
+    fn fade(&mut self) {
+        if self.floor_turns < fading::FADING_GRACE
+            || !self.floor_turns.is_multiple_of(fading::FADING_INTERVAL)
+            || self.fading >= FADING_MAX
+        {
+            return;
+        }
+        let before = self.fading_stage();
+        self.fading += 1;
+        if self.fading_stage() > before {
+            self.log(self.fading_stage().message());
+        }
+        if self.fading == FADING_MAX && !self.game_over {
+            self.summon_erinys();
+        }
+    }
+
+    fn ease_fading(&mut self, amount: u32) {
+        let before = self.fading_stage();
+        self.fading = self.fading.saturating_sub(amount);
+        let stage = self.fading_stage();
+        if stage == Stage::Whole && before > Stage::Whole {
+            self.log(stage.message());
+        } else if stage < before {
+            self.log(&format!("You feel a little more whole, though still {}.", stage.name().to_lowercase()));
+        }
+    }
+
+    /// Earn an achievement, with a pop-up if it's the first time
+    fn achieve(&mut self, achievement: Achievement) {
+        if self.achievements.contains(&achievement) {
+            return;
+        }
+        self.achievements.push(achievement);
+        self.popups.push((achievement, POPUP_TURNS));
+        self.log(&format!("Achievement: {}!", achievement.name()));
+    }
+
+    pub fn has_heat(&self, heat: Heat) -> bool {
+        self.heat.contains(&heat)
+    }
+
+    /// Under the Stubborn Dead, every monster on a new floor gets a fifth more HP
+    fn stubborn_dead(&mut self) {
+        if !self.has_heat(Heat::StubbornDead) {
+            return;
+        }
+        for e in self.entities.iter_mut().filter(|e| e.kind.is_enemy()) {
+            e.max_hp = e.max_hp * 6 / 5;
+            e.hp = e.max_hp;
+        }
+    }
+
+    /// An Erinys arrives in the room farthest from the shade, and comes straight for it
+    fn summon_erinys(&mut self) {
+        let (px, py) = (self.player.x, self.player.y);
+        let Some(room) = self.map.rooms.iter().copied().max_by_key(|r| {
+            let (cx, cy) = r.center();
+            (cx as i32 - px).abs() + (cy as i32 - py).abs()
+        }) else { return };
+        let spot = (room.y1..room.y2)
+            .flat_map(|y| (room.x1..room.x2).map(move |x| (x as i32, y as i32)))
+            .find(|&(x, y)| {
+                self.map.tiles[y as usize][x as usize].walkable()
+                    && !self.entities.iter().any(|e| e.alive && e.x == x && e.y == y)
+                    && (x, y) != (px, py)
+            });
+        if let Some((x, y)) = spot {
+            self.entities.push(Entity::enemy(x, y, EntityKind::Erinys, self.depth));
+            self.log("A shriek rises somewhere on the terrace. An Erinys has your scent.");
+        }
+    }
+
+    /// Player accuracy, after gear and blindness
+    pub fn player_accuracy(&self) -> i32 {
+        let acc = self.player.accuracy + self.inventory.gear_stats().accuracy;
+        if self.blind_turns > 0 { acc - 20 } else { acc }
+    }
+
+    pub fn player_attack(&self) -> i32 {
+        self.player.effective_attack() + self.inventory.gear_stats().attack
+    }
+
+    pub fn player_defense(&self) -> i32 {
+        self.player.defense + self.inventory.gear_stats().defense
+    }
+
+    pub fn player_evasion(&self) -> i32 {
+        self.player.evasion + self.inventory.gear_stats().evasion
+    }
+
+    pub fn weapon(&self) -> Option<EntityKind> {
+        self.inventory.equipped_kind(Slot::Weapon)
+    }
+
+    /// Damage type of the player's melee blows
+    pub fn player_damage_type(&self) -> DamageType {
+        let dt = self.weapon().map_or(self.player.damage_type, |w| w.weapon_damage_type());
+        if dt == DamageType::Physical && self.has_boon(Boon::TorchesOfHecate) {
+            DamageType::Fire
+        } else {
+            dt
+        }
+    }
+
+    /// (player hits it, it hits player) percentages for an enemy in melee, glancing blows included
+    pub fn hit_chances(&self, idx: usize) -> (i32, i32) {
+        let e = &self.entities[idx];
+        (
+            connect_chance(self.player_accuracy(), e.evasion),
+            connect_chance(e.accuracy, self.player_evasion()),
+        )
     }
 
     pub fn player_action(&mut self, action: Action) {
@@ -111,10 +364,42 @@ impl Game {
                 self.log("You wait. The meadows don't care.");
             }
             Action::Pickup => self.try_pickup(),
-            Action::Descend => self.try_descend(),
+            Action::Descend => {
+                // Charon's prompt opens for free, as often as it takes
+                if !self.try_descend() {
+                    return;
+                }
+            }
             Action::Interact => self.try_interact(),
-            Action::UseAbility(idx) => self.try_ability(idx),
+            Action::UseAbility(idx) => self.try_ability(idx, None),
+            Action::UseAbilityAt(idx, x, y) => self.try_ability(idx, Some((x, y))),
             Action::Look => self.look_around(),
+            // A refused throw, use, drop or change of gear takes no time
+            Action::Throw(slot, tx, ty) => {
+                if !self.try_throw(slot, tx, ty) {
+                    return;
+                }
+            }
+            Action::UseItem(slot) => {
+                if !self.use_item(slot) {
+                    return;
+                }
+            }
+            Action::DropItem(slot) => {
+                if !self.drop_item(slot) {
+                    return;
+                }
+            }
+            Action::Equip(slot) => {
+                if !self.equip_item(slot) {
+                    return;
+                }
+            }
+            Action::Unequip(slot) => {
+                if !self.unequip_item(slot) {
+                    return;
+                }
+            }
         }
 
         // Tick buffs
@@ -130,20 +415,90 @@ impl Game {
                 self.log("Your vision clears.");
             }
         }
+        if self.poison_turns > 0 {
+            self.poison_turns -= 1;
+            self.log("Poison burns through you. -2 HP.");
+            self.hurt_player(2, "poison", DamageType::Physical, "poison");
+            if self.poison_turns == 0 && !self.game_over {
+                self.log("The poison has run its course.");
+            }
+        }
+        if self.phase_turns > 0 {
+            self.phase_turns -= 1;
+            if self.phase_turns == 0 {
+                self.end_phase();
+            }
+        }
+        if let Some((i, turns)) = self.tether {
+            if turns <= 1 || !self.entities[i].alive {
+                self.tether = None;
+                self.log("The soul tether frays and snaps.");
+            } else {
+                self.tether = Some((i, turns - 1));
+            }
+        }
+        if let Some((x, y, turns)) = self.decoy {
+            self.decoy = if turns > 1 { Some((x, y, turns - 1)) } else { None };
+        }
+        let hasted = self.haste_turns > 0;
+        if hasted {
+            self.haste_turns -= 1;
+            if self.haste_turns == 0 {
+                self.log("The world resumes its usual pace.");
+            }
+        }
+        if self.wither_turns > 0 {
+            self.wither_turns -= 1;
+            if self.wither_turns == 0 {
+                self.log("The withering lets go of you.");
+            }
+        }
+        if self.beacon_turns > 0 {
+            self.beacon_turns -= 1;
+            if self.beacon_turns == 0 {
+                self.log("The dead stop listening for you.");
+            }
+        }
+
+        // Achievement pop-ups fade after a few turns
+        for popup in &mut self.popups {
+            popup.1 -= 1;
+        }
+        self.popups.retain(|p| p.1 > 0);
 
         // Tick ability cooldowns
         for ab in &mut self.abilities {
             ab.tick();
         }
 
-        // Enemy turns
-        if !self.game_over {
+        // Enemy turns (a hasted shade gets every other turn free)
+        let free_turn = hasted && self.turns.is_multiple_of(2);
+        if !self.game_over && !free_turn {
             self.enemy_turns();
+            // Ares' bargain: every third turn the monsters get another go
+            if self.has_boon(Boon::PactOfAres) && self.turns.is_multiple_of(3) && !self.game_over {
+                self.enemy_turns();
+            }
         }
         self.turns += 1;
+        self.floor_turns += 1;
+        self.fade();
+        if self.has_heat(Heat::Hunted) && self.floor_turns == ERINYS_DELAY && !self.game_over {
+            self.summon_erinys();
+        }
+
+        // Withering and fading won't let HP rise past the cap
+        if self.player.hp > self.hp_cap() {
+            self.player.hp = self.hp_cap();
+        }
+
+        self.hp_history.push(self.player.hp.max(0));
+        if self.hp_history.len() > RECAP_TURNS {
+            self.hp_history.remove(0);
+        }
 
         // Ambient flavor
-        if let Some(msg) = flavor::ambient_message(self.depth, self.turns) {
+        if let Some(msg) = flavor::ambient_message(self.depth, self.turns, self.background) {
             self.log(msg);
         }
 
@@ -159,117 +514,919 @@ impl Game {
         if !self.map.in_bounds(nx, ny) {
             return;
         }
-        if !self.map.tiles[ny as usize][nx as usize].walkable() {
+        let walkable = self.map.tiles[ny as usize][nx as usize].walkable();
+        let inner = nx > 0 && ny > 0 && (nx as usize) < MAP_W - 1 && (ny as usize) < MAP_H - 1;
+        let phasing = self.phase_turns > 0 && inner;
+        if !walkable && !phasing {
             return;
         }
 
-        // Check for enemy at target
+        // Check for enemy at target; a possessed one steps aside
         if let Some(idx) = self.entities.iter().position(|e| e.x == nx && e.y == ny && e.alive && e.kind.is_enemy()) {
-            self.attack_entity(idx);
+            if self.entities[idx].possessed_turns > 0 {
+                self.entities[idx].x = self.player.x;
+                self.entities[idx].y = self.player.y;
+                self.player.x = nx;
+                self.player.y = ny;
+            } else {
+                self.attack_entity(idx);
+            }
             return;
         }
 
+        // Bumping into Hermes opens his stall
+        if let Some(idx) = self.shop_at(nx, ny) {
+            self.open_shop(idx);
+            return;
+        }
+
+        // A spear reaches past the empty tile to the enemy behind it
+        if self.weapon().is_some_and(|w| w.weapon_trait() == WeaponTrait::Reach)
+            && self.map.tiles[ny as usize][nx as usize].transparent()
+        {
+            let (rx, ry) = (nx + dx, ny + dy);
+            if let Some(idx) = self.enemy_at(rx, ry) {
+                if self.map.visible[ry as usize][rx as usize] {
+                    self.strike(idx);
+                    return;
+                }
+            }
+        }
+
         self.player.x = nx;
         self.player.y = ny;
+        if walkable {
+            self.phase_anchor = (nx, ny);
+        }
+    }
+
+    /// Phasing wears off; anyone still inside the stone is pushed back out
+    fn end_phase(&mut self) {
+        let (px, py) = (self.player.x, self.player.y);
+        if self.map.tiles[py as usize][px as usize].walkable() {
+            self.log("You feel solid again.");
+        } else {
+            (self.player.x, self.player.y) = self.phase_anchor;
+            self.log("The stone remembers it is solid. It spits you back out.");
+        }
     }
 
     fn attack_entity(&mut self, idx: usize) {
+        let (tx, ty) = (self.entities[idx].x, self.entities[idx].y);
+        self.strike(idx);
+
+        // A bident's second tine finds another foe beside you
+        if self.weapon().is_some_and(|w| w.weapon_trait() == WeaponTrait::Cleave) {
+            let (px, py) = (self.player.x, self.player.y);
+            let second = self.entities.iter().position(|e| {
+                e.alive && e.kind.is_enemy() && (e.x, e.y) != (tx, ty)
+                    && (e.x - px).abs() <= 1 && (e.y - py).abs() <= 1
+            });
+            if let Some(j) = second {
+                self.strike(j);
+            }
+        }
+    }
+
+    /// A single melee blow from the player, with weapon riders
+    fn strike(&mut self, idx: usize) {
         let mut rng = rand::thread_rng();
-        let atk = self.player.effective_attack();
+        let atk = self.player_attack();
         let def = self.entities[idx].defense;
-        let damage = (atk - def + rng.gen_range(-1..=2)).max(0);
+        let (acc, eva) = (self.player_accuracy(), self.entities[idx].evasion);
+        let chance = connect_chance(acc, eva);
+        let result = roll_attack(&mut rng, acc, eva);
+        let damage = roll_damage(&mut rng, result, atk, def);
+        let damage = self.resisted(idx, damage, self.player_damage_type());
+        let result = settle(result, damage);
 
-        self.entities[idx].hp -= damage;
         let name = self.entities[idx].kind.name();
 
-        if damage > 0 {
-            self.log(&format!("You strike the {} for {} damage.", name, damage));
-        } else {
-            self.log(&format!("You strike the {} but deal no damage.", name));
+        match result {
+            HitResult::Miss => self.log(&format!("You miss the {}. ({}%)", name, chance)),
+            HitResult::Critical => self.log(&format!("A critical blow! You rend the {} for {} damage.", name, damage)),
+            HitResult::Glancing => self.log(&format!("Your blow glances off the {}. {} damage. ({}%)", name, damage, chance)),
+            HitResult::Hit if damage > 0 => self.log(&format!("You strike the {} for {} damage. ({}%)", name, damage, chance)),
+            HitResult::Hit => self.log(&format!("You strike the {} but deal no damage.", name)),
         }
+        if damage > 0 && self.weapon().is_some_and(|w| w.weapon_trait() == WeaponTrait::Bleed) {
+            self.entities[idx].bleed_turns = BLEED_TURNS;
+        }
+        self.hurt_entity(idx, damage);
+    }
+
+    /// Scale damage by the target's resistance, learning it if it's unusual
+    fn resisted(&mut self, idx: usize, damage: i32, dt: DamageType) -> i32 {
+        let kind = self.entities[idx].kind;
+        let pct = kind.resistance(dt);
+        if damage > 0 && pct != 100 && !self.known_resists.contains(&(kind, dt)) {
+            self.known_resists.push((kind, dt));
+            let verdict = match pct {
+                0 => "is untouched by",
+                1..=99 => "shrugs off some of the",
+                _ => "recoils from the",
+            };
+            self.log(&format!("The {} {} {} damage!", kind.name(), verdict, dt.name()));
+        }
+        damage * pct / 100
+    }
+
+    /// Resistances and weaknesses the player has discovered for a kind
+    pub fn known_resistances(&self, kind: EntityKind) -> String {
+        let mut weak = Vec::new();
+        let mut strong = Vec::new();
+        for &(k, dt) in &self.known_resists {
+            if k != kind { continue; }
+            if kind.resistance(dt) > 100 { weak.push(dt.name()); } else { strong.push(dt.name()); }
+        }
+        let mut parts = Vec::new();
+        if !weak.is_empty() { parts.push(format!("weak: {}", weak.join("/"))); }
+        if !strong.is_empty() { parts.push(format!("resists: {}", strong.join("/"))); }
+        parts.join(", ")
+    }
 
+    fn hurt_entity(&mut self, idx: usize, damage: i32) {
+        self.entities[idx].hp -= damage;
         if self.entities[idx].hp <= 0 {
             self.entities[idx].alive = false;
+            tally(&mut self.kills, self.entities[idx].kind);
+            if self.entities[idx].kind == EntityKind::Erinys {
+                self.achieve(Achievement::KindlyOnes);
+            }
+            let name = self.entities[idx].kind.name();
             self.log(&format!("The {} dissolves into mist.", name));
+            self.gain_xp(level::xp_for_kill(self.entities[idx].kind, self.depth));
+            let mut rng = rand::thread_rng();
+            let (x, y) = (self.entities[idx].x, self.entities[idx].y);
+            // Now and then something golden is left behind
+            if rng.gen_ratio(1, 4) {
+                self.entities.push(Entity::item(x, y, EntityKind::Ichor));
+            }
+            if self.has_boon(Boon::WealthOfPlouton) && rng.gen_ratio(1, 3) {
+                self.entities.push(Entity::item(x, y, EntityKind::Obol));
+            }
+            if self.has_boon(Boon::HadesTithe) && self.player.hp < self.hp_cap() {
+                let heal = 2.min(self.hp_cap() - self.player.hp);
+                self.player.hp += heal;
+                self.log(&format!("Hades' tithe. +{} HP.", heal));
+            }
+            if self.has_boon(Boon::Bloodlust) {
+                self.player.strength_turns = self.player.strength_turns.max(3);
+            }
+        }
+    }
+
+    fn hurt_player(&mut self, damage: i32, source: &str, damage_type: DamageType, note: &'static str) {
+        // A soul tether passes half of every wound along
+        let damage = match self.tether {
+            Some((i, _)) if self.entities[i].alive && damage > 1 => {
+                let shared = damage / 2;
+                let name = self.entities[i].kind.name();
+                self.log(&format!("The tether carries {} of it to the {}.", shared, name));
+                self.hurt_entity(i, shared);
+                damage - shared
+            }
+            _ => damage,
+        };
+        self.damage_events.push(DamageEvent {
+            turn: self.turns,
+            source: source.to_string(),
+            amount: damage,
+            damage_type,
+            note,
+        });
+        if self.damage_events.len() > RECAP_EVENTS {
+            self.damage_events.remove(0);
+        }
+
+        self.player.hp -= damage;
+        if self.player.hp <= 0 && !self.game_over {
+            self.player.alive = false;
+            self.game_over = true;
+            self.killed_by = source.to_string();
+        }
+    }
+
+    fn enemy_at(&self, x: i32, y: i32) -> Option<usize> {
+        self.entities.iter().position(|e| e.x == x && e.y == y && e.alive && e.kind.is_enemy())
+    }
+
+    /// Indices of enemies the player can see, nearest first
+    pub fn visible_enemies(&self) -> Vec<usize> {
+        let mut found: Vec<(i32, usize)> = self.entities.iter().enumerate()
+            .filter(|(_, e)| e.alive && e.kind.is_enemy() && self.map.visible[e.y as usize][e.x as usize])
+            .map(|(i, e)| ((e.x - self.player.x).pow(2) + (e.y - self.player.y).pow(2), i))
+            .collect();
+        found.sort();
+        found.into_iter().map(|(_, i)| i).collect()
+    }
+
+    /// Visible enemies that aren't fighting for the player, nearest first
+    pub fn hostile_enemies(&self) -> Vec<usize> {
+        self.visible_enemies().into_iter().filter(|&i| self.entities[i].possessed_turns == 0).collect()
+    }
+
+    /// Quick-throw: aim the first pebble in the pack
+    pub fn begin_quick_throw(&mut self) {
+        match self.inventory.find(EntityKind::Pebble) {
+            Some(slot) => self.begin_targeting(TargetMode::Throw(slot)),
+            None => self.log("You have no pebbles. Throw something else from your pack [i]."),
+        }
+    }
+
+    pub fn begin_targeting(&mut self, mode: TargetMode) {
+        if let TargetMode::Throw(slot) = mode {
+            if self.inventory.get(slot).is_none() {
+                self.log("You have nothing to throw.");
+                return;
+            }
+            if self.stuck_to_you(slot) {
+                return;
+            }
+        }
+        let range = mode.range();
+        let (px, py) = (self.player.x, self.player.y);
+        let (x, y) = match mode {
+            TargetMode::Direction { .. } => {
+                let (dx, dy) = self.default_dash_direction();
+                self.reach_toward(dx, dy, range)
+            }
+            _ => self.hostile_enemies().into_iter()
+                .map(|i| (self.entities[i].x, self.entities[i].y))
+                .find(|&(x, y)| (x - px).abs().max((y - py).abs()) <= range)
+                .unwrap_or((px, py)),
+        };
+        self.targeting = Some(Targeting::new(mode, x, y));
+        self.log(&format!("{} where? [Tab] next target  [Enter] confirm  [Esc] cancel", mode.verb()));
+    }
+
+    /// The tile `range` steps from the player in a direction, pulled in to stay on the map
+    fn reach_toward(&self, dx: i32, dy: i32, range: i32) -> (i32, i32) {
+        let (px, py) = (self.player.x, self.player.y);
+        (1..=range).rev()
+            .map(|step| (px + dx * step, py + dy * step))
+            .find(|&(x, y)| self.map.in_bounds(x, y))
+            .unwrap_or((px, py))
+    }
+
+    pub fn move_cursor(&mut self, dx: i32, dy: i32) {
+        let (px, py) = (self.player.x, self.player.y);
+        // Aiming a direction: one key points the whole way
+        if let Some(TargetMode::Direction { range, .. }) = self.targeting.as_ref().map(|t| t.mode) {
+            let (x, y) = self.reach_toward(dx, dy, range);
+            if let Some(t) = &mut self.targeting {
+                (t.x, t.y) = (x, y);
+            }
+            return;
+        }
+        if let Some(t) = &mut self.targeting {
+            let nx = t.x + dx;
+            let ny = t.y + dy;
+            let range = t.mode.range();
+            if self.map.in_bounds(nx, ny) && (nx - px).abs().max((ny - py).abs()) <= range {
+                t.x = nx;
+                t.y = ny;
+            }
+        }
+    }
+
+    /// Jump the cursor to the next visible enemy in range
+    pub fn cycle_target(&mut self) {
+        let (px, py) = (self.player.x, self.player.y);
+        let Some(range) = self.targeting.as_ref().map(|t| t.mode.range()) else { return };
+        let targets: Vec<(i32, i32)> = self.hostile_enemies().into_iter()
+            .map(|i| (self.entities[i].x, self.entities[i].y))
+            .filter(|&(x, y)| (x - px).abs().max((y - py).abs()) <= range)
+            .collect();
+        if let Some(t) = &mut self.targeting {
+            if targets.is_empty() {
+                return;
+            }
+            t.cycle = (t.cycle + 1) % targets.len();
+            (t.x, t.y) = targets[t.cycle];
         }
     }
 
+    pub fn cancel_targeting(&mut self) {
+        self.targeting = None;
+        self.log("You lower your hand.");
+    }
+
+    /// Resolve the cursor into an action, or None if the target is invalid
+    pub fn confirm_target(&mut self) -> Option<Action> {
+        let t = self.targeting.as_ref()?;
+        if t.x == self.player.x && t.y == self.player.y {
+            self.log("Choose a target other than yourself.");
+            return None;
+        }
+        let t = self.targeting.take()?;
+        match t.mode {
+            TargetMode::Throw(slot) => Some(Action::Throw(slot, t.x, t.y)),
+            TargetMode::Ability { slot, .. } | TargetMode::Direction { slot, .. } => {
+                Some(Action::UseAbilityAt(slot, t.x, t.y))
+            }
+        }
+    }
+
+    /// The tiles a projectile would cross, stopping at the first wall or creature
+    pub fn projectile_path(&self, x0: i32, y0: i32, x1: i32, y1: i32) -> Vec<(i32, i32)> {
+        let mut path = Vec::new();
+        for (x, y) in line(x0, y0, x1, y1) {
+            if !self.map.in_bounds(x, y) || !self.map.tiles[y as usize][x as usize].transparent() {
+                break;
+            }
+            path.push((x, y));
+            if self.enemy_at(x, y).is_some() {
+                break;
+            }
+        }
+        path
+    }
+
+    /// What the player calls an item: potions go by their looks until identified
+    pub fn item_name(&self, kind: EntityKind) -> String {
+        match kind {
+            EntityKind::Potion(effect) => self.potions.display_name(effect),
+            _ => kind.name().to_string(),
+        }
+    }
+
+    pub fn item_description(&self, kind: EntityKind) -> &'static str {
+        match kind {
+            EntityKind::Potion(effect) if !self.potions.is_known(effect) => "Unknown. Drink to find out",
+            _ => kind.description(),
+        }
+    }
+
+    /// Throw one of what's in `slot`. Returns false if nothing left the pack.
+    fn try_throw(&mut self, slot: usize, tx: i32, ty: i32) -> bool {
+        if self.stuck_to_you(slot) {
+            return false;
+        }
+        let Some((kind, curse)) = self.inventory.remove_one(slot) else {
+            self.log("You have nothing to throw.");
+            return false;
+        };
+        let name = self.item_name(kind);
+
+        let path = self.projectile_path(self.player.x, self.player.y, tx, ty);
+        let Some(&(lx, ly)) = path.last() else {
+            self.log(&format!("The {} clatters against the wall at your feet.", name));
+            self.land_thrown(kind, curse, self.player.x, self.player.y);
+            return true;
+        };
+
+        if let Some(idx) = self.enemy_at(lx, ly) {
+            let mut rng = rand::thread_rng();
+            let def = self.entities[idx].defense;
+            let (acc, eva) = (self.player_accuracy() - RANGED_PENALTY, self.entities[idx].evasion);
+            let result = roll_attack(&mut rng, acc, eva);
+            let damage = roll_damage(&mut rng, result, kind.throw_damage(), def);
+            let damage = self.resisted(idx, damage, DamageType::Physical);
+            let target = self.entities[idx].kind.name();
+            if result == HitResult::Miss {
+                self.log(&format!("Your {} sails past the {}. ({}%)", name, target, connect_chance(acc, eva)));
+            } else if damage > 0 {
+                self.log(&format!("Your {} strikes the {} for {} damage.", name, target, damage));
+            } else {
+                self.log(&format!("Your {} glances off the {}.", name, target));
+            }
+            self.hurt_entity(idx, damage);
+        } else if !kind.fragile() {
+            self.log(&format!("The {} skitters across the stone.", name));
+        }
+        self.land_thrown(kind, curse, lx, ly);
+        true
+    }
+
+    /// A thrown item comes to rest, unless it breaks
+    fn land_thrown(&mut self, kind: EntityKind, curse: Option<Curse>, x: i32, y: i32) {
+        if kind.fragile() {
+            self.log(&format!("The {} is lost on impact.", self.item_name(kind)));
+        } else {
+            let mut item = Entity::item(x, y, kind);
+            item.curse = curse;
+            self.entities.push(item);
+        }
+    }
+
+    /// Cursed gear that is equipped refuses to leave. Logs why and returns true if so.
+    fn stuck_to_you(&mut self, slot: usize) -> bool {
+        let Some(stack) = self.inventory.stacks.get_mut(slot) else { return false };
+        if !stack.stuck() {
+            return false;
+        }
+        stack.curse_known = true;
+        let name = stack.kind.name();
+        self.log(&format!("The {} will not come away. It is cursed.", name));
+        true
+    }
+
+    /// The name shown in the pack, with any curse the player knows of
+    pub fn stack_name(&self, stack: &ItemStack) -> String {
+        match stack.curse {
+            Some(curse) if stack.curse_known => format!("cursed {} ({})", self.item_name(stack.kind), curse.name()),
+            _ => self.item_name(stack.kind),
+        }
+    }
+
+    pub fn stack_description(&self, stack: &ItemStack) -> String {
+        match stack.curse {
+            Some(curse) if stack.curse_known => {
+                let effect = if stack.kind.equip_slot().is_some() { curse.description() } else { curse.used_description() };
+                format!("{}. Cursed: {}", self.item_description(stack.kind), effect)
+            }
+            _ => self.item_description(stack.kind).to_string(),
+        }
+    }
+
+    /// Highest HP the shade can reach, halved while something withering is worn or lingers
+    pub fn hp_cap(&self) -> i32 {
+        let cap = if self.inventory.wearing_curse(Curse::Withering) || self.wither_turns > 0 {
+            self.player.max_hp / 2
+        } else {
+            self.player.max_hp
+        };
+        cap * self.fading_stage().hp_percent() / 100
+    }
+
+    /// Wash the curses off everything carried, and any a cursed consumable left
+    /// behind. Returns how many were lifted.
+    fn lift_curses(&mut self) -> usize {
+        let mut lifted = 0;
+        for turns in [&mut self.wither_turns, &mut self.beacon_turns] {
+            if *turns > 0 {
+                *turns = 0;
+                lifted += 1;
+            }
+        }
+        for stack in &mut self.inventory.stacks {
+            if stack.curse.take().is_some() {
+                lifted += 1;
+            }
+            stack.curse_known = false;
+        }
+        lifted
+    }
+
     fn try_pickup(&mut self) {
         let px = self.player.x;
         let py = self.player.y;
 
         if let Some(idx) = self.entities.iter().position(|e| e.x == px && e.y == py && e.alive && e.kind.is_item()) {
             let kind = self.entities[idx].kind;
-            self.entities[idx].alive = false;
 
-            match kind {
-                EntityKind::Nectar => {
-                    let heal = 10.min(self.player.max_hp - self.player.hp);
-                    self.player.hp += heal;
-                    self.log(&format!("You drink the nectar. +{} HP.", heal));
+            if kind == EntityKind::Obol {
+                self.entities[idx].alive = false;
+                self.obols += 1;
+                self.log("You pick up an obol. Payment for the ferryman.");
+                return;
+            }
+            if kind == EntityKind::Ichor {
+                self.entities[idx].alive = false;
+                self.ichor += 1;
+                self.log("You gather a drop of ichor. It hums. [a] to spend it.");
+                return;
+            }
+            if kind == EntityKind::Echo {
+                self.entities[idx].alive = false;
+                self.learn_from_echo();
+                self.ease_fading(fading::ECHO_RELIEF);
+                return;
+            }
+            if kind == EntityKind::Memory {
+                self.entities[idx].alive = false;
+                self.remember();
+                return;
+            }
+
+            if self.inventory.add_item(kind, self.entities[idx].curse) {
+                self.entities[idx].alive = false;
+                tally(&mut self.found, kind);
+                self.log(&format!("You pick up the {}.", self.item_name(kind)));
+            } else {
+                self.log("Your hands are full. Drop something first.");
+            }
+        } else {
+            self.log("Nothing to pick up here.");
+        }
+    }
+
+    /// Use what's in `slot`. Returns false if nothing was done, so no turn passes.
+    fn use_item(&mut self, slot: usize) -> bool {
+        let Some(kind) = self.inventory.get(slot) else { return false };
+        let curse = self.inventory.stacks[slot].curse.filter(|_| kind.equip_slot().is_none());
+        if curse == Some(Curse::Leaden) {
+            self.inventory.remove_one(slot);
+            self.log(&format!("The {} is dull as lead in your hands and crumbles to nothing. Cursed!", self.item_name(kind)));
+            return true;
+        }
+
+        match kind {
+            EntityKind::Nectar => {
+                if self.player.hp >= self.hp_cap() {
+                    self.log("You are already whole. You keep the nectar for later.");
+                    return false;
+                }
+                let heal = 10.min(self.hp_cap() - self.player.hp);
+                self.player.hp += heal;
+                self.log(&format!("You drink the nectar. +{} HP.", heal));
+            }
+            EntityKind::Moly => {
+                self.player.strength_turns = 15;
+                self.log("You eat the moly. Power surges through you.");
+            }
+            EntityKind::Potion(effect) => self.drink_potion(effect),
+            EntityKind::SibylLeaf => {
+                let unknown = self.inventory.stacks.iter().find_map(|s| match s.kind {
+                    EntityKind::Potion(e) if !self.potions.is_known(e) => Some(e),
+                    _ => None,
+                });
+                let mut named = Vec::new();
+                for stack in &mut self.inventory.stacks {
+                    if let Some(curse) = stack.curse.filter(|_| !stack.curse_known) {
+                        stack.curse_known = true;
+                        named.push((stack.kind.name(), curse));
+                    }
                 }
-                EntityKind::Obol => {
-                    self.obols += 1;
-                    self.log("You pick up an obol. Payment for the ferryman.");
+                if unknown.is_none() && named.is_empty() {
+                    self.log("The leaf's verses are blank. You carry no mysteries.");
+                    return false;
                 }
-                EntityKind::Moly => {
-                    self.player.strength_turns = 15;
-                    self.log("You eat the moly. Power surges through you.");
+                if let Some(effect) = unknown {
+                    let look = self.potions.appearance(effect);
+                    self.potions.learn(effect);
+                    self.log(&format!("The Sibyl's words rustle: the {} is a {}.", look, effect.name()));
+                }
+                for (name, curse) in named {
+                    self.log(&format!("The Sibyl whispers of the {}: {}.", name, curse.name()));
+                }
+            }
+            _ if kind.equip_slot().is_some() => {
+                return if self.inventory.stacks[slot].equipped {
+                    self.unequip_item(slot)
+                } else {
+                    self.equip_item(slot)
+                };
+            }
+            _ => {
+                self.log(&format!("You turn the {} over in your hands. Perhaps throw it.", self.item_name(kind)));
+                return false;
+            }
+        }
+        self.inventory.remove_one(slot);
+        if let Some(curse) = curse {
+            self.curse_lingers(curse);
+        }
+        true
+    }
+
+    /// A cursed consumable, once used, leaves its curse on the shade for a while
+    fn curse_lingers(&mut self, curse: Curse) {
+        match curse {
+            Curse::Withering => {
+                self.wither_turns = CURSE_TURNS;
+                self.log("A chill creeps after it. Cursed: your strength withers!");
+            }
+            Curse::Beacon => {
+                self.beacon_turns = CURSE_TURNS;
+                self.log("Something in it calls out. Cursed: every monster on the floor hears you!");
+            }
+            Curse::Leaden => {}
+        }
+    }
+
+    fn drink_potion(&mut self, effect: PotionEffect) {
+        let look = self.potions.appearance(effect);
+        if self.potions.learn(effect) {
+            self.log(&format!("You drink from the {}. It was a {}!", look, effect.name()));
+        } else {
+            self.log(&format!("You drink the {}.", effect.name()));
+        }
+
+        match effect {
+            PotionEffect::Healing => {
+                let heal = 20.min(self.hp_cap() - self.player.hp).max(0);
+                self.player.hp += heal;
+                self.log(&format!("Warmth floods back into you. +{} HP.", heal));
+            }
+            PotionEffect::Poison => {
+                self.poison_turns = 5;
+                self.log("It tastes of hemlock. Your insides knot.");
+            }
+            PotionEffect::Haste => {
+                self.haste_turns = 10;
+                self.log("The meadows slow to a crawl around you.");
+            }
+            PotionEffect::Blindness => {
+                self.blind_turns = self.blind_turns.max(8);
+                self.log("Darkness pours in behind your eyes.");
+            }
+            PotionEffect::Amnesia => {
+                for row in &mut self.map.revealed {
+                    row.fill(false);
                 }
-                EntityKind::StygianBlade => {
-                    self.player.attack += 2;
-                    self.log("You take up the Stygian Blade. +2 attack.");
+                self.log("Where were you? The floor you walked is gone from memory.");
+            }
+            PotionEffect::SeeAll => {
+                for row in &mut self.map.revealed {
+                    row.fill(true);
                 }
-                _ => {}
+                self.log("The walls turn to mist. You see the whole terrace.");
+            }
+        }
+    }
+
+    /// Put on what's in `slot`. Returns false if it stays in the pack.
+    fn equip_item(&mut self, slot: usize) -> bool {
+        let Some(kind) = self.inventory.get(slot) else { return false };
+        let Some(gear_slot) = kind.equip_slot() else {
+            self.log(&format!("You can't equip the {}.", kind.name()));
+            return false;
+        };
+        if self.inventory.stacks[slot].equipped {
+            return false;
+        }
+        if let Some(old) = self.inventory.equipped(gear_slot) {
+            if self.stuck_to_you(old) {
+                return false;
             }
+            self.unequip_item(old);
+        }
+        let stack = &mut self.inventory.stacks[slot];
+        stack.equipped = true;
+        let revealed = stack.curse.filter(|_| !stack.curse_known);
+        stack.curse_known |= stack.curse.is_some();
+        self.log(&format!("You {} the {}. {}.", gear_slot.verb(), kind.name(), kind.description()));
+        if let Some(curse) = revealed {
+            self.log(&format!("It tightens around you. Cursed: {}!", curse.description()));
+        }
+        true
+    }
+
+    /// Take off what's in `slot`. Returns false if it stays on.
+    fn unequip_item(&mut self, slot: usize) -> bool {
+        if self.stuck_to_you(slot) {
+            return false;
+        }
+        let Some(stack) = self.inventory.stacks.get_mut(slot) else { return false };
+        if !stack.equipped {
+            return false;
+        }
+        stack.equipped = false;
+        let name = stack.kind.name();
+        self.log(&format!("You remove the {}.", name));
+        true
+    }
+
+    /// Set down one of what's in `slot`. Returns false if nothing was dropped.
+    fn drop_item(&mut self, slot: usize) -> bool {
+        if self.stuck_to_you(slot) {
+            return false;
+        }
+        let Some((kind, curse)) = self.inventory.remove_one(slot) else { return false };
+        let mut item = Entity::item(self.player.x, self.player.y, kind);
+        item.curse = curse;
+        self.entities.push(item);
+        self.log(&format!("You set down the {}.", self.item_name(kind)));
+        true
+    }
+
+    pub fn open_inventory(&mut self) {
+        if self.inventory.is_empty() {
+            self.log("You carry nothing but memories. Not even those.");
         } else {
-            self.log("Nothing to pick up here.");
+            self.inventory_cursor = Some(0);
+        }
+    }
+
+    pub fn move_inventory_cursor(&mut self, delta: i32) {
+        let len = self.inventory.len() as i32;
+        if let Some(cur) = &mut self.inventory_cursor {
+            if len > 0 {
+                *cur = (*cur as i32 + delta).rem_euclid(len) as usize;
+            }
         }
     }
 
-    fn try_descend(&mut self) {
+    /// Take the stairs. Returns false if that took no time: there were none, or Charon is asking.
+    fn try_descend(&mut self) -> bool {
         let px = self.player.x as usize;
         let py = self.player.y as usize;
 
-        if self.map.tiles[py][px] != crate::map::Tile::Stair {
+        if self.map.tiles[py][px] != Tile::Stair {
             self.log("There are no stairs here.");
-            return;
+            return false;
+        }
+
+        if self.depth >= STYX_DEPTH {
+            self.log("The bank bends. Far off, light, and no ferryman needed.");
+            self.finish(Ending::StyxBank);
+            return true;
         }
 
         if self.depth >= 7 {
-            self.game_over = true;
-            self.victory = true;
-            return;
+            self.log("Black water. A skiff, and an old man with his hand out.");
+            self.log(&format!("Charon wants {} obols. You have {}.", CHARON_FARE, self.obols));
+            self.log("[p] Pay the fare  [w] Walk the bank of the Styx  [s] Sit and wait  [Esc] Not yet");
+            self.charon_prompt = true;
+            return false;
         }
 
-        self.depth += 1;
-        let heal = 5.min(self.player.max_hp - self.player.hp);
+        self.descend_to(self.depth + 1);
+        true
+    }
+
+    fn descend_to(&mut self, depth: u32) {
+        if !self.spotted {
+            self.achieve(Achievement::Unseen);
+        }
+        self.spotted = false;
+        self.ease_fading(fading::DESCENT_RELIEF);
+        self.depth = depth;
+        let mend = if self.has_boon(Boon::PactOfHades) {
+            0
+        } else if self.has_boon(Boon::SpringReturns) {
+            15
+        } else {
+            5
+        };
+        let heal = mend.min(self.hp_cap() - self.player.hp);
         if heal > 0 {
             self.player.hp += heal;
             self.log(&format!("The descent restores you slightly. +{} HP.", heal));
         }
         self.log(flavor::depth_message(self.depth));
 
-        self.map = Map::new(self.depth);
+        let mut rng = floor_rng(self.seed, self.depth);
+        self.map = Map::new(self.depth, &mut rng);
         let (px, py) = self.map.rooms[0].center();
         self.player.x = px as i32;
         self.player.y = py as i32;
 
         self.entities.clear();
         self.shrines.clear();
+        self.shops.clear();
+        self.fonts.clear();
+        self.tether = None;
+        self.decoy = None;
+        self.floor_turns = 0;
+        self.phase_anchor = (self.player.x, self.player.y);
         for i in 1..self.map.rooms.len() {
             let room = self.map.rooms[i];
-            populate_room(&room, self.depth, &mut self.entities, &self.map);
+            populate_room(&room, &mut self.entities, &self.map, &mut rng);
+        }
+        self.stubborn_dead();
+        // The Styx bank is a gauntlet: no gods, no merchants
+        if self.depth == STYX_DEPTH {
+            self.floor_rng = rng;
+            let fov = self.effective_fov();
+            compute_fov(&mut self.map, self.player.x, self.player.y, fov);
+            return;
         }
+
         // Shrine every other floor
-        if self.depth % 2 == 0 && self.map.rooms.len() > 3 {
+        if self.depth.is_multiple_of(2) && self.map.rooms.len() > 3 && !self.has_heat(Heat::Godless) {
             let shrine_room = &self.map.rooms[self.map.rooms.len() / 2];
             let (sx, sy) = shrine_room.center();
-            self.shrines.push(Shrine::new(sx as i32, sy as i32));
+            self.shrines.push(Shrine::new(sx as i32, sy as i32, &self.boons, &mut rng));
+        }
+        // Hermes visits about half the floors, in the room after the shrine's
+        let shop_room = self.map.rooms.len() / 2 + 1;
+        if shop_room + 1 < self.map.rooms.len() && rng.gen_bool(0.5) {
+            let (hx, hy) = self.map.rooms[shop_room].center();
+            let (hx, hy) = (hx as i32, hy as i32);
+            self.entities.retain(|e| e.x != hx || e.y != hy);
+            self.shops.push(Shop::new(hx, hy, self.depth, &mut rng));
+            self.log("You hear coins clink somewhere on this terrace.");
+        }
+        // Some floors hold a font of Lethe, in the room before the shrine's
+        if let Some(font_room) = (self.map.rooms.len() / 2).checked_sub(1) {
+            if font_room > 0 && self.depth >= 3 && rng.gen_bool(0.4) {
+                let (fx, fy) = self.map.rooms[font_room].center();
+                let (fx, fy) = (fx as i32, fy as i32);
+                self.entities.retain(|e| e.x != fx || e.y != fy);
+                self.fonts.push(LetheFont { x: fx, y: fy, used: false });
+            }
+        }
+        // Now and then an echo of an older shade lingers, with something to teach
+        if self.depth >= 2 && self.map.rooms.len() >= 2 && rng.gen_bool(0.35) {
+            let room = self.map.rooms[rng.gen_range(1..self.map.rooms.len())];
+            let ex = rng.gen_range(room.x1 as i32..room.x2 as i32);
+            let ey = rng.gen_range(room.y1 as i32..room.y2 as i32);
+            let taken = self.entities.iter().any(|e| e.alive && e.x == ex && e.y == ey)
+                || self.shrines.iter().any(|s| s.x == ex && s.y == ey)
+                || self.shop_at(ex, ey).is_some()
+                || self.fonts.iter().any(|f| f.x == ex && f.y == ey);
+            if !taken {
+                self.entities.push(Entity::item(ex, ey, EntityKind::Echo));
+                self.log("Something on this terrace is whispering your name.");
+            }
+        }
+
+        self.floor_rng = rng;
+        self.place_fragment();
+
+        let fov = self.effective_fov();
+        compute_fov(&mut self.map, self.player.x, self.player.y, fov);
+    }
+
+    /// Each of the first floors keeps one piece of the shade's past, somewhere past the first room
+    fn place_fragment(&mut self) {
+        if self.depth as usize > FRAGMENT_COUNT {
+            return;
+        }
+        // Somewhere in a room other than the first, where the shade wakes
+        if self.map.rooms.len() >= 2 {
+            for _ in 0..20 {
+                let room = self.map.rooms[self.floor_rng.gen_range(1..self.map.rooms.len())];
+                let mx = self.floor_rng.gen_range(room.x1 as i32..room.x2 as i32);
+                let my = self.floor_rng.gen_range(room.y1 as i32..room.y2 as i32);
+                if self.fragment_fits(mx, my) {
+                    self.entities.push(Entity::item(mx, my, EntityKind::Memory));
+                    return;
+                }
+            }
+        }
+        // Failing that, anywhere at all: no floor goes without its fragment
+        let free: Vec<(i32, i32)> = (0..MAP_H as i32)
+            .flat_map(|y| (0..MAP_W as i32).map(move |x| (x, y)))
+            .filter(|&(x, y)| (x, y) != (self.player.x, self.player.y) && self.fragment_fits(x, y))
+            .collect();
+        if let Some(&(mx, my)) = free.choose(&mut self.floor_rng) {
+            self.entities.push(Entity::item(mx, my, EntityKind::Memory));
+        }
+    }
+
+    /// Whether a memory fragment could lie at (x, y): open floor with nothing on it
+    fn fragment_fits(&self, x: i32, y: i32) -> bool {
+        self.map.tiles[y as usize][x as usize].walkable()
+            && !self.entities.iter().any(|e| e.alive && e.x == x && e.y == y)
+            && !self.shrines.iter().any(|s| s.x == x && s.y == y)
+            && self.shop_at(x, y).is_none()
+            && !self.fonts.iter().any(|f| f.x == x && f.y == y)
+    }
+
+    /// Pick up a memory fragment: the next piece of the life comes back
+    fn remember(&mut self) {
+        if self.fragments >= FRAGMENT_COUNT {
+            return;
+        }
+        let fragment = self.background.fragments()[self.fragments];
+        self.fragments += 1;
+        self.log(&format!("A memory surfaces ({}/{}). {}", self.fragments, FRAGMENT_COUNT, fragment));
+        if self.fragments == FRAGMENT_COUNT {
+            self.log("You remember who you were. All of it.");
+        }
+        self.ease_fading(fading::MEMORY_RELIEF);
+    }
+
+    fn finish(&mut self, ending: Ending) {
+        self.game_over = true;
+        self.ending = Some(ending);
+        if !ending.is_victory() {
+            return;
+        }
+        if !self.found.iter().any(|(k, _)| *k == EntityKind::Nectar) {
+            self.achieve(Achievement::Abstinent);
+        }
+        if self.obols == 0 {
+            self.achieve(Achievement::Pauper);
+        }
+        if heat::total(&self.heat) >= 5 {
+            self.achieve(Achievement::Hellfire);
         }
+        if self.daily.is_some() {
+            self.achieve(Achievement::DailyBread);
+        }
+    }
 
-        let fov = self.effective_fov();
-        compute_fov(&mut self.map, self.player.x, self.player.y, fov);
+    pub fn pay_charon(&mut self) {
+        if self.obols < CHARON_FARE {
+            self.log(&format!("Charon counts your {} obols and does not move.", self.obols));
+            return;
+        }
+        self.obols -= CHARON_FARE;
+        self.charon_prompt = false;
+        self.log("The coins vanish into his robe. The skiff pushes off.");
+        self.finish(Ending::Elysium);
+    }
+
+    pub fn walk_styx_bank(&mut self) {
+        self.charon_prompt = false;
+        self.log("You turn from the skiff and follow the bank. Things live in the reeds.");
+        self.descend_to(STYX_DEPTH);
+    }
+
+    pub fn wait_on_shore(&mut self) {
+        self.charon_prompt = false;
+        self.log("You sit down on the black sand. He will come back. He has to.");
+        self.finish(Ending::Shore);
+    }
+
+    pub fn leave_charon(&mut self) {
+        self.charon_prompt = false;
+        self.log("You step back from the water. Charon does not watch you go.");
     }
 
     fn try_interact(&mut self) {
@@ -278,18 +1435,26 @@ impl Game {
 
         // Check for shrine
         if let Some(idx) = self.shrines.iter().position(|s| s.x == px && s.y == py && !s.used) {
-            let shrine = &self.shrines[idx];
-            let a_name = shrine.boon_a.name();
-            let a_desc = shrine.boon_a.description();
-            let b_name = shrine.boon_b.name();
-            let b_desc = shrine.boon_b.description();
-            self.log(&format!("A shrine of grey stone. Choose your blessing:"));
-            self.log(&format!("[A] {} — {}", a_name, a_desc));
-            self.log(&format!("[B] {} — {}", b_name, b_desc));
+            let god = self.shrines[idx].god;
+            self.log(god.shrine_text());
             self.shrine_prompt = Some(idx);
             return;
         }
 
+        if let Some(idx) = self.fonts.iter().position(|f| f.x == px && f.y == py && !f.used) {
+            self.fonts[idx].used = true;
+            let lifted = self.lift_curses();
+            for row in &mut self.map.revealed {
+                row.fill(false);
+            }
+            if lifted > 0 {
+                self.log("You drink from the font. Whatever bound you lets go, and so does the floor's memory.");
+            } else {
+                self.log("You drink from the font. The floor you walked slips from your memory.");
+            }
+            return;
+        }
+
         self.log("Nothing to interact with here.");
     }
 
@@ -297,28 +1462,41 @@ impl Game {
         let mut things: Vec<String> = Vec::new();
 
         // Visible enemies
-        for e in &self.entities {
+        for (idx, e) in self.entities.iter().enumerate() {
             if !e.alive || !e.kind.is_enemy() { continue; }
             if !self.map.visible[e.y as usize][e.x as usize] { continue; }
-            let dist = ((e.x - self.player.x).abs() + (e.y - self.player.y).abs());
+            let dist = (e.x - self.player.x).abs() + (e.y - self.player.y).abs();
             let proximity = if dist <= 2 { "nearby" } else if dist <= 5 { "close" } else { "distant" };
-            things.push(format!("{} {} (HP:{}/{})", proximity, e.kind.name(), e.hp, e.max_hp));
+            let (to_hit, to_be_hit) = self.hit_chances(idx);
+            let resists = self.known_resistances(e.kind);
+            let resists = if resists.is_empty() { String::new() } else { format!(", {}", resists) };
+            things.push(format!("{} {} (HP:{}/{}, hit {}%, hits you {}%{})",
+                proximity, e.kind.name(), e.hp, e.max_hp, to_hit, to_be_hit, resists));
         }
 
         // Visible items
         for e in &self.entities {
             if !e.alive || !e.kind.is_item() { continue; }
             if !self.map.visible[e.y as usize][e.x as usize] { continue; }
-            things.push(e.kind.name().to_string());
+            things.push(self.item_name(e.kind));
+        }
+
+        if self.shops.iter().any(|s| self.map.visible[s.y as usize][s.x as usize]) {
+            things.push("Hermes, leaning on his caduceus".to_string());
+        }
+
+        for f in &self.fonts {
+            if !self.map.visible[f.y as usize][f.x as usize] { continue; }
+            things.push(if f.used { "dry font".to_string() } else { "font of Lethe (≈)".to_string() });
         }
 
         // Shrines
         for s in &self.shrines {
             if !self.map.visible[s.y as usize][s.x as usize] { continue; }
             if s.used {
-                things.push("spent shrine".to_string());
+                things.push(format!("spent shrine of {}", s.god.name()));
             } else {
-                things.push("shrine (Ω)".to_string());
+                things.push(format!("shrine of {} (Ω)", s.god.name()));
             }
         }
 
@@ -329,183 +1507,753 @@ impl Game {
         }
     }
 
-    pub fn choose_shrine_boon(&mut self, choose_a: bool) {
-        if let Some(idx) = self.shrine_prompt {
-            let boon = if choose_a {
-                self.shrines[idx].boon_a
-            } else {
-                self.shrines[idx].boon_b
-            };
-            self.shrines[idx].used = true;
-            self.shrine_prompt = None;
-            self.apply_boon(boon);
+    pub fn shop_at(&self, x: i32, y: i32) -> Option<usize> {
+        self.shops.iter().position(|s| s.x == x && s.y == y)
+    }
+
+    fn open_shop(&mut self, idx: usize) {
+        self.log("Hermes grins. \"Obols, little shade? Everything has a price.\"");
+        self.shop_prompt = Some(idx);
+    }
+
+    pub fn leave_shop(&mut self) {
+        self.shop_prompt = None;
+        self.log("\"Come back when your purse is heavier.\"");
+    }
+
+    pub fn ware_name(&self, ware: Ware) -> String {
+        match ware {
+            Ware::Item(kind) => self.item_name(kind),
+            Ware::Mending => "Mending".to_string(),
+            Ware::BoonReroll => "A Word with the Gods".to_string(),
+            Ware::Uncursing => "Uncursing".to_string(),
+        }
+    }
+
+    pub fn ware_description(&self, ware: Ware) -> &'static str {
+        match ware {
+            Ware::Item(kind) => self.item_description(kind),
+            Ware::Mending => "Restore to full HP",
+            Ware::BoonReroll => "Reroll the boons of this floor's shrine",
+            Ware::Uncursing => "Lift the curses from everything you carry",
+        }
+    }
+
+    /// Buy the offer at `offer` from the open shop. Costs obols, not a turn.
+    pub fn buy(&mut self, offer: usize) {
+        let Some(shop_idx) = self.shop_prompt else { return };
+        let Some(o) = self.shops[shop_idx].offers.get(offer) else { return };
+        let (ware, price) = (o.ware, o.price);
+        if o.sold {
+            self.log("\"Sold already. I don't keep spares.\"");
+            return;
+        }
+        if self.obols < price {
+            self.log(&format!("\"{} obols, shade. You have {}.\"", price, self.obols));
+            return;
+        }
+
+        match ware {
+            Ware::Item(kind) => {
+                if !self.inventory.add(kind) {
+                    self.log("Your pack is full. Hermes shrugs.");
+                    return;
+                }
+                self.log(&format!("You buy the {} for {} obols.", self.item_name(kind), price));
+            }
+            Ware::Mending => {
+                if self.player.hp >= self.hp_cap() {
+                    self.log("\"You look whole enough to me.\"");
+                    return;
+                }
+                // Trading takes no time, so no mending with something at your throat
+                let pressed = self.hostile_enemies().iter().any(|&i| {
+                    let e = &self.entities[i];
+                    (e.x - self.player.x).abs() <= 1 && (e.y - self.player.y).abs() <= 1
+                });
+                if pressed {
+                    self.log("\"Deal with your friend first. I don't heal what's about to be torn again.\"");
+                    return;
+                }
+                self.player.hp = self.hp_cap();
+                self.log(&format!("Hermes' caduceus touches your brow. Healed to full, for {} obols.", price));
+            }
+            Ware::BoonReroll => {
+                let Some(shrine) = self.shrines.iter_mut().find(|s| !s.used) else {
+                    self.log("\"No shrine left on this floor for me to bargain with.\"");
+                    return;
+                };
+                shrine.reroll(&self.boons, &mut self.floor_rng);
+                let names: Vec<&str> = shrine.offers.iter().map(|b| b.name()).collect();
+                let god = shrine.god.name();
+                self.log(&format!("Hermes whispers to {}. The shrine now offers {}.", god, names.join(" or ")));
+            }
+            Ware::Uncursing => {
+                let lingering = self.wither_turns > 0 || self.beacon_turns > 0;
+                if !lingering && !self.inventory.stacks.iter().any(|s| s.curse.is_some()) {
+                    self.log("\"Nothing on you worth unbinding, shade.\"");
+                    return;
+                }
+                self.lift_curses();
+                self.log(&format!("Hermes mutters a word older than Olympus. Your curses fall away, for {} obols.", price));
+            }
         }
+        self.obols -= price;
+        self.shops[shop_idx].offers[offer].sold = true;
+    }
+
+    pub fn choose_shrine_boon(&mut self, choice: usize) {
+        let Some(idx) = self.shrine_prompt else { return };
+        let Some(&boon) = self.shrines[idx].offers.get(choice) else { return };
+        self.shrines[idx].used = true;
+        self.shrine_prompt = None;
+        self.apply_boon(boon);
+    }
+
+    pub fn has_boon(&self, boon: Boon) -> bool {
+        self.boons.contains(&boon)
     }
 
     fn apply_boon(&mut self, boon: Boon) {
+        self.boons.push(boon);
+        if self.boons.len() >= 5 {
+            self.achieve(Achievement::Favoured);
+        }
         match boon {
             Boon::VitalityOfErebus => {
                 self.player.max_hp += 10;
-                self.player.hp = self.player.max_hp;
+                self.player.hp = self.hp_cap();
                 self.log("Erebus grants vitality. +10 max HP, healed to full.");
             }
+            Boon::HadesTithe => self.log("Hades takes his tithe of every death, and shares it with you."),
+            Boon::WealthOfPlouton => self.log("Plouton, lord of buried riches, will pay you for your kills."),
+            Boon::GraceOfPersephone => {
+                let heal = 15.min(self.hp_cap() - self.player.hp).max(0);
+                self.player.hp += heal;
+                self.log(&format!("Persephone grants grace. +{} HP.", heal));
+            }
+            Boon::MercyOfPersephone => {
+                if self.lift_curses() > 0 {
+                    self.log("Persephone grants mercy. The curses on you wither like winter.");
+                } else {
+                    self.log("Persephone grants mercy, though you carry no curse to lift.");
+                }
+            }
+            Boon::SpringReturns => self.log("Persephone promises spring. Every descent will mend you more."),
+            Boon::EyesOfNyx => {
+                self.fov_radius += 3;
+                self.log("Nyx grants sight. You see further into the dark.");
+            }
+            Boon::VeilOfNyx => {
+                self.player.evasion += 15;
+                self.log("Nyx draws her veil around you. +15 evasion.");
+            }
+            Boon::ShadowDecoys => self.log("Nyx lends you her shadows. Your dashes will leave one behind."),
+            Boon::SwiftnessOfHermes => {
+                for ab in &mut self.abilities {
+                    ab.cooldown = (ab.cooldown - 3).max(0);
+                }
+                self.log("Hermes grants swiftness. All cooldowns reduced.");
+            }
+            Boon::PurseOfHermes => {
+                self.obols += 3;
+                self.log("Hermes tosses you a purse. +3 obols.");
+            }
+            Boon::Quicksilver => self.log("Quicksilver runs in your veins. Your abilities return sooner."),
             Boon::WrathOfAres => {
                 self.player.attack += 3;
                 self.log("Ares grants fury. +3 attack.");
             }
+            Boon::Bloodlust => self.log("Ares laughs. Every kill will leave you hungrier."),
+            Boon::SpearOfAres => {
+                self.player.attack += 5;
+                self.log("Ares lends you his own spear's edge. +5 attack.");
+            }
             Boon::AegisOfAthena => {
                 self.player.defense += 2;
                 self.log("Athena grants protection. +2 defense.");
             }
-            Boon::SwiftnessOfHermes => {
+            Boon::OwlSight => {
+                self.player.accuracy += 10;
+                self.log("Athena's owl sees for you. +10 accuracy.");
+            }
+            Boon::Riposte => self.log("Athena teaches you to answer every miss."),
+            Boon::CrossroadsToll => {
+                self.ichor += 3;
+                self.log("Hecate leaves three drops of ichor at the crossroads. +3 ichor.");
+            }
+            Boon::FavourOfHecate => {
+                let upgradable: Vec<usize> = (0..self.abilities.len())
+                    .filter(|&i| self.abilities[i].next_effect().is_some())
+                    .collect();
+                if upgradable.is_empty() {
+                    self.ichor += 3;
+                    self.log("Hecate finds nothing left to sharpen. She leaves 3 ichor instead.");
+                } else {
+                    let i = upgradable[rand::thread_rng().gen_range(0..upgradable.len())];
+                    self.abilities[i].rank += 1;
+                    let ab = &self.abilities[i];
+                    let (name, rank) = (ab.def.name, ability::rank_numeral(ab.rank));
+                    self.log(&format!("Hecate grants favour. {} rises to rank {}.", name, rank));
+                }
+            }
+            Boon::TorchesOfHecate => self.log("Hecate's torches catch in your hands. Your blows will burn."),
+            Boon::PactOfAres => {
+                self.player.attack += 5;
+                self.log("Ares grips your arm. +5 attack. Somewhere, the monsters quicken.");
+            }
+            Boon::PactOfHades => {
+                self.player.max_hp += 20;
+                self.player.hp = (self.player.hp + 20).min(self.hp_cap());
+                self.log("Hades seals it. +20 max HP. The stairs will no longer mend you.");
+            }
+            Boon::PactOfNyx => {
+                self.player.evasion += 25;
+                self.fov_radius = (self.fov_radius - 3).max(2);
+                self.log("Nyx wraps you in her dark. +25 evasion, and the dark comes closer.");
+            }
+            Boon::PactOfHecate => {
                 for ab in &mut self.abilities {
-                    ab.cooldown = (ab.cooldown - 3).max(0);
+                    if ab.next_effect().is_some() {
+                        ab.rank += 1;
+                    }
                 }
-                self.log("Hermes grants swiftness. All cooldowns reduced.");
+                self.log("Hecate's three faces smile. Every ability rises. Every use will bleed you.");
             }
-            Boon::GraceOfPersephone => {
-                let heal = 15.min(self.player.max_hp - self.player.hp);
-                self.player.hp += heal;
-                self.log(&format!("Persephone grants grace. +{} HP.", heal));
+        }
+    }
+
+    /// Give something up at the open shrine. Costs no turn.
+    pub fn make_offering(&mut self, offering: Offering) {
+        let Some(idx) = self.shrine_prompt else { return };
+        let shrine = &self.shrines[idx];
+        if shrine.offering_made {
+            self.log(&format!("{} has already taken your offering.", shrine.god.name()));
+            return;
+        }
+        let (god, offers) = (shrine.god, shrine.offers.clone());
+
+        let extra = match offering {
+            Offering::Blood => Boon::draw_extra(god, Rarity::Rare, &offers, &self.boons, &mut self.floor_rng),
+            Offering::Flesh => Boon::draw_extra(god, Rarity::Epic, &offers, &self.boons, &mut self.floor_rng)
+                .or_else(|| Boon::draw_extra(god, Rarity::Rare, &offers, &self.boons, &mut self.floor_rng)),
+            Offering::Coin => None,
+        };
+        match offering {
+            Offering::Blood | Offering::Flesh if extra.is_none() => {
+                self.log(&format!("{} has nothing rarer to give. Your offering is refused.", god.name()));
+                return;
             }
-            Boon::EyesOfNyx => {
-                self.fov_radius += 3;
-                self.log("Nyx grants sight. You see further into the dark.");
+            Offering::Blood if self.player.hp <= 8 => {
+                self.log("You haven't the blood to spare.");
+                return;
+            }
+            Offering::Flesh if self.player.max_hp <= 10 => {
+                self.log("There is too little of you left to give.");
+                return;
+            }
+            Offering::Coin if self.obols < 3 => {
+                self.log(&format!("The offering is 3 obols. You have {}.", self.obols));
+                return;
+            }
+            _ => {}
+        }
+
+        match offering {
+            Offering::Blood => {
+                self.player.hp -= 8;
+                self.log("You open your palm over the stone. -8 HP.");
+            }
+            Offering::Flesh => {
+                self.player.max_hp -= 5;
+                self.player.hp = self.player.hp.min(self.hp_cap());
+                self.log("Something of you stays on the altar for good. -5 max HP.");
+            }
+            Offering::Coin => {
+                self.obols -= 3;
+                self.log("You leave three obols on the stone. -3 obols.");
+            }
+        }
+
+        let shrine = &mut self.shrines[idx];
+        shrine.offering_made = true;
+        match extra {
+            Some(boon) => {
+                shrine.offers.push(boon);
+                self.log(&format!("{} is pleased, and offers {} as well.", god.name(), boon.name()));
+            }
+            None => {
+                shrine.reroll(&self.boons, &mut self.floor_rng);
+                self.log(&format!("{} considers you again, and offers something new.", god.name()));
             }
         }
     }
 
-    fn try_ability(&mut self, idx: usize) {
-        if idx >= self.abilities.len() {
+    /// Take the open shrine's god up on its bargain. Doesn't use up the shrine's boon.
+    pub fn strike_pact(&mut self) {
+        let Some(idx) = self.shrine_prompt else { return };
+        let shrine = &self.shrines[idx];
+        let god = shrine.god;
+        let Some(pact) = Boon::pact(god).filter(|p| !shrine.pact_struck && !self.has_boon(*p)) else {
+            self.log(&format!("{} has no bargain for you.", god.name()));
+            return;
+        };
+        self.shrines[idx].pact_struck = true;
+        self.apply_boon(pact);
+    }
+
+    pub fn leave_shrine(&mut self) {
+        self.shrine_prompt = None;
+        self.log("You step away from the shrine.");
+    }
+
+    /// Fire an instant ability, or start aiming one that needs a target.
+    /// Returns the action to take now, if any.
+    pub fn begin_ability(&mut self, idx: usize) -> Option<Action> {
+        let state = self.abilities.get(idx)?;
+        match state.def.target {
+            AbilityTarget::Instant => Some(Action::UseAbility(idx)),
+            AbilityTarget::Enemy(range) => {
+                if !self.ability_blocked(idx) {
+                    self.begin_targeting(TargetMode::Ability { slot: idx, range });
+                }
+                None
+            }
+            AbilityTarget::Direction => {
+                let range = state.effect().reach();
+                if !self.ability_blocked(idx) {
+                    self.begin_targeting(TargetMode::Direction { slot: idx, range });
+                }
+                None
+            }
+        }
+    }
+
+    /// HP an ability takes to use, with Hecate's bargain on top
+    pub fn ability_hp_cost(&self, def: &AbilityDef) -> i32 {
+        if self.has_boon(Boon::PactOfHecate) {
+            def.hp_cost + 2
+        } else {
+            def.hp_cost
+        }
+    }
+
+    /// Whether the ability in `idx` can't be used right now. Logs why.
+    fn ability_blocked(&mut self, idx: usize) -> bool {
+        let state = &self.abilities[idx];
+        let def = state.def;
+        if !state.ready() {
+            let cd = state.cooldown;
+            self.log(&format!("{} is on cooldown ({} turns).", def.name, cd));
+            return true;
+        }
+        let cost = self.ability_hp_cost(def);
+        if cost > 0 && self.player.hp <= cost {
+            self.log(&format!("You are too faint to pay for {} ({} HP).", def.name, cost));
+            return true;
+        }
+        false
+    }
+
+    fn try_ability(&mut self, idx: usize, tile: Option<(i32, i32)>) {
+        if idx >= self.abilities.len() || self.ability_blocked(idx) {
             return;
         }
-        if !self.abilities[idx].ready() {
-            let cd = self.abilities[idx].cooldown;
-            let name = self.abilities[idx].ability.name();
-            self.log(&format!("{} is on cooldown ({} turns).", name, cd));
+        let def = self.abilities[idx].def;
+
+        let target = match def.target {
+            AbilityTarget::Instant | AbilityTarget::Direction => None,
+            AbilityTarget::Enemy(range) => {
+                let (px, py) = (self.player.x, self.player.y);
+                let found = tile.and_then(|(x, y)| self.enemy_at(x, y)).filter(|&i| {
+                    let e = &self.entities[i];
+                    self.map.visible[e.y as usize][e.x as usize]
+                        && (e.x - px).abs().max((e.y - py).abs()) <= range
+                });
+                if found.is_none() {
+                    self.log(&format!("There is no enemy there for {}.", def.name));
+                    return;
+                }
+                found
+            }
+        };
+
+        if !self.fire_effect(def, self.abilities[idx].effect(), target, tile) {
             return;
         }
+        self.abilities[idx].trigger();
+        if self.has_boon(Boon::Quicksilver) {
+            self.abilities[idx].cooldown = self.abilities[idx].cooldown * 3 / 4;
+        }
+        if self.has_heat(Heat::Sluggish) {
+            self.abilities[idx].cooldown *= 2;
+        }
+        let cost = self.ability_hp_cost(def);
+        if cost > 0 {
+            self.player.hp -= cost;
+            self.log(&format!("It costs you {} HP.", cost));
+        }
+    }
 
-        let ability = self.abilities[idx].ability;
-        match ability {
-            Ability::Dash => {
-                // Dash: we need a direction. For simplicity, dash toward nearest enemy
-                // or in the last move direction. Let's dash away from nearest enemy.
-                if let Some((dx, dy)) = self.find_dash_direction() {
-                    self.abilities[idx].trigger();
-                    // Find furthest valid position
-                    let (mut fx, mut fy) = (self.player.x, self.player.y);
-                    for step in 1..=2 {
-                        let sx = self.player.x + dx * step;
-                        let sy = self.player.y + dy * step;
-                        if self.map.in_bounds(sx, sy) && self.map.tiles[sy as usize][sx as usize].walkable() {
-                            fx = sx;
-                            fy = sy;
-                        } else {
-                            break;
-                        }
-                    }
-                    if fx != self.player.x || fy != self.player.y {
-                        self.player.x = fx;
-                        self.player.y = fy;
-                        self.log("You dash through the grey air.");
-                    } else {
-                        self.log("Nowhere to dash.");
-                        self.abilities[idx].cooldown = 0; // refund
-                    }
+    /// Carry out an ability's effect. Returns false if nothing happened, so no cooldown is spent.
+    fn fire_effect(&mut self, def: &'static AbilityDef, effect: Effect, target: Option<usize>, tile: Option<(i32, i32)>) -> bool {
+        let (px, py) = (self.player.x, self.player.y);
+        match effect {
+            Effect::Dash { distance, trail } => {
+                let Some((tx, ty)) = tile else { return false };
+                let path = self.dash_path(tx, ty, distance);
+                let Some((lx, ly)) = self.dash_landing(&path) else {
+                    self.log("Nowhere to dash.");
+                    return false;
+                };
+                let passed: Vec<usize> = path.iter()
+                    .take_while(|&&tile| tile != (lx, ly))
+                    .filter_map(|&(x, y)| self.enemy_at(x, y))
+                    .collect();
+                self.player.x = lx;
+                self.player.y = ly;
+                if self.has_boon(Boon::ShadowDecoys) {
+                    self.decoy = Some((px, py, 3));
+                }
+                if passed.is_empty() {
+                    self.log(def.flavor);
                 } else {
-                    self.log("No direction to dash.");
-                }
-            }
-            Ability::SpectralScream => {
-                self.abilities[idx].trigger();
-                let px = self.player.x;
-                let py = self.player.y;
-                let mut hit = 0;
-                for e in &mut self.entities {
-                    if !e.alive || !e.kind.is_enemy() { continue; }
-                    let dist = ((e.x - px).pow(2) + (e.y - py).pow(2)) as f64;
-                    if dist <= 9.0 && self.map.visible[e.y as usize][e.x as usize] {
-                        let damage = 6;
-                        e.hp -= damage;
-                        hit += 1;
-                        if e.hp <= 0 {
-                            e.alive = false;
-                        }
+                    let names: Vec<&str> = passed.iter().map(|&i| self.entities[i].kind.name()).collect();
+                    self.log(&format!("{} Straight through the {}. It shivers.", def.flavor, names.join(" and the ")));
+                }
+                // Higher ranks leave a trail of cold behind
+                if trail > 0 {
+                    for i in passed {
+                        let damage = self.resisted(i, trail, DamageType::Spectral);
+                        let name = self.entities[i].kind.name();
+                        self.log(&format!("Your wake freezes the {} for {} damage.", name, damage));
+                        self.hurt_entity(i, damage);
                     }
                 }
-                if hit > 0 {
-                    self.log(&format!("You scream into the void. {} enemies shattered.", hit));
-                    // (deaths already handled by hp <= 0 check above)
+            }
+            Effect::Burst { radius, damage, damage_type, fear } => {
+                let targets: Vec<usize> = self.hostile_enemies().into_iter()
+                    .filter(|&i| {
+                        let e = &self.entities[i];
+                        (e.x - px).pow(2) + (e.y - py).pow(2) <= radius * radius
+                    })
+                    .collect();
+                if targets.is_empty() {
+                    self.log(&format!("{} It finds no one.", def.flavor));
                 } else {
-                    self.log("Your scream echoes through empty halls.");
+                    self.log(&format!("{} {} enemies shattered.", def.flavor, targets.len()));
+                }
+                for i in targets {
+                    let damage = self.resisted(i, damage, damage_type);
+                    self.hurt_entity(i, damage);
+                    let empusa = self.entities[i].kind == EntityKind::Empusa;
+                    if empusa && !self.entities[i].alive && std::ptr::eq(def, &ability::SPECTRAL_SCREAM) {
+                        self.achieve(Achievement::Banshee);
+                    }
+                    if fear > 0 && self.entities[i].alive {
+                        self.entities[i].fear_turns = self.entities[i].fear_turns.max(fear);
+                    }
+                }
+                if fear > 0 && self.entities.iter().any(|e| e.alive && e.fear_turns == fear) {
+                    self.log("What's left of them turns and flees.");
                 }
             }
-            Ability::LethesTouch => {
-                self.abilities[idx].trigger();
-                let heal = 15.min(self.player.max_hp - self.player.hp);
+            Effect::Heal { amount, cleanse } => {
+                let heal = amount.min(self.hp_cap() - self.player.hp).max(0);
                 self.player.hp += heal;
-                self.log(&format!("You touch the waters of Lethe. You forget your pain. +{} HP.", heal));
+                self.log(&format!("{} +{} HP.", def.flavor, heal));
+                if cleanse && (self.blind_turns > 0 || self.poison_turns > 0 || self.defense_stripped > 0) {
+                    self.blind_turns = 0;
+                    self.poison_turns = 0;
+                    self.player.defense += self.defense_stripped;
+                    self.defense_stripped = 0;
+                    self.log("Your wounds go with it: blindness, poison, torn armour. Gone.");
+                }
+            }
+            Effect::Possess { turns } => {
+                let Some(i) = target else { return false };
+                self.entities[i].possessed_turns = turns;
+                let name = self.entities[i].kind.name();
+                self.log(&format!("{} The {} turns on its own kind.", def.flavor, name));
+            }
+            Effect::Phase { turns } => {
+                self.phase_turns = turns;
+                self.phase_anchor = (px, py);
+                self.log(def.flavor);
+            }
+            Effect::Bind { radius, turns } => {
+                let targets: Vec<usize> = self.hostile_enemies().into_iter()
+                    .filter(|&i| {
+                        let e = &self.entities[i];
+                        (e.x - px).pow(2) + (e.y - py).pow(2) <= radius * radius
+                    })
+                    .collect();
+                if targets.is_empty() {
+                    self.log(&format!("{} It binds nothing but the asphodel.", def.flavor));
+                } else {
+                    self.log(&format!("{} {} enemies are held fast.", def.flavor, targets.len()));
+                }
+                for i in targets {
+                    self.entities[i].bound_turns = self.entities[i].bound_turns.max(turns);
+                }
+            }
+            Effect::Tether { turns } => {
+                let Some(i) = target else { return false };
+                self.tether = Some((i, turns));
+                let name = self.entities[i].kind.name();
+                self.log(&format!("{} It ties you to the {}. What hurts you hurts it.", def.flavor, name));
             }
         }
+        true
     }
 
-    fn find_dash_direction(&self) -> Option<(i32, i32)> {
-        // Find nearest visible enemy, dash away from it
-        let mut nearest: Option<(f64, i32, i32)> = None;
-        for e in &self.entities {
-            if !e.alive || !e.kind.is_enemy() { continue; }
-            if !self.map.visible[e.y as usize][e.x as usize] { continue; }
-            let dist = ((e.x - self.player.x).pow(2) + (e.y - self.player.y).pow(2)) as f64;
-            if nearest.is_none() || dist < nearest.unwrap().0 {
-                nearest = Some((dist, e.x, e.y));
-            }
+    pub fn toggle_ability_menu(&mut self) {
+        self.ability_menu = !self.ability_menu;
+    }
+
+    fn gain_xp(&mut self, amount: u32) {
+        self.xp += amount;
+        while self.xp >= level::xp_to_next(self.level) {
+            self.xp -= level::xp_to_next(self.level);
+            self.level += 1;
+            self.levels_pending += 1;
+            self.log(&format!("You feel more yourself. Level {}.", self.level));
+        }
+        if self.levels_pending > 0 && self.growth_offers.is_empty() {
+            self.growth_offers = Growth::offer(&self.abilities, &mut rand::thread_rng());
         }
+    }
 
-        if let Some((_, ex, ey)) = nearest {
-            // Dash away from enemy
-            let dx = (self.player.x - ex).signum();
-            let dy = (self.player.y - ey).signum();
-            if dx == 0 && dy == 0 {
-                Some((1, 0)) // default right
-            } else {
-                Some((dx, dy))
+    /// Take one of a level-up's offers. Costs no turn.
+    pub fn choose_growth(&mut self, idx: usize) {
+        let Some(&growth) = self.growth_offers.get(idx) else { return };
+        match growth {
+            Growth::Vitality => {
+                self.player.max_hp += 8;
+                self.player.hp = (self.player.hp + 8).min(self.hp_cap());
+                self.log("You remember having a body. +8 max HP.");
             }
+            Growth::Might => {
+                self.player.attack += 1;
+                self.log("You remember how to hit. +1 attack.");
+            }
+            Growth::Guard => {
+                self.player.defense += 1;
+                self.log("You remember how to flinch. +1 defense.");
+            }
+            Growth::Aim => {
+                self.player.accuracy += 5;
+                self.log("You remember how to look. +5 accuracy.");
+            }
+            Growth::Grace => {
+                self.player.evasion += 5;
+                self.log("You remember how to step aside. +5 evasion.");
+            }
+            Growth::Deepen(slot) => {
+                self.abilities[slot].rank += 1;
+                let ab = &self.abilities[slot];
+                let (name, rank, summary) = (ab.def.name, ability::rank_numeral(ab.rank), ab.effect().summary());
+                self.log(&format!("Something old wakes in you. {} rank {}: {}.", name, rank, summary));
+            }
+        }
+        self.levels_pending -= 1;
+        self.growth_offers = if self.levels_pending > 0 {
+            Growth::offer(&self.abilities, &mut rand::thread_rng())
         } else {
-            // No enemies visible, dash right by default
-            Some((1, 0))
+            Vec::new()
+        };
+    }
+
+    /// Spend ichor to raise the ability in `idx` a rank. Costs no turn.
+    pub fn upgrade_ability(&mut self, idx: usize) {
+        let Some(ab) = self.abilities.get(idx) else { return };
+        let (name, cost) = (ab.def.name, ability::upgrade_cost(ab.rank));
+        if ab.next_effect().is_none() {
+            self.log(&format!("{} can rise no higher.", name));
+            return;
+        }
+        if self.ichor < cost {
+            self.log(&format!("{} needs {} ichor. You have {}.", name, cost, self.ichor));
+            return;
+        }
+        self.ichor -= cost;
+        self.abilities[idx].rank += 1;
+        let ab = &self.abilities[idx];
+        let (rank, summary) = (ability::rank_numeral(ab.rank), ab.effect().summary());
+        self.log(&format!("The ichor burns into you. {} rank {}: {}.", name, rank, summary));
+    }
+
+    /// Pick up an echo: learn an ability this shade doesn't know yet
+    fn learn_from_echo(&mut self) {
+        let unknown: Vec<&'static AbilityDef> = ability::POOL.iter().copied()
+            .filter(|def| !self.abilities.iter().any(|ab| std::ptr::eq(ab.def, *def)))
+            .collect();
+        if unknown.is_empty() || self.abilities.len() >= MAX_ABILITIES {
+            self.log("The echo murmurs, but it has nothing left to teach you.");
+            return;
         }
+        let def = unknown[self.floor_rng.gen_range(0..unknown.len())];
+        self.abilities.push(AbilityState::new(def));
+        let key = ability::key_for(self.abilities.len() - 1);
+        self.log(&format!("An echo of an older shade settles into you. You learn {} [{}].", def.name, key));
+        self.log(def.description);
+    }
+
+    /// Where the dash cursor starts: away from the nearest visible enemy, else to the right
+    fn default_dash_direction(&self) -> (i32, i32) {
+        let (px, py) = (self.player.x, self.player.y);
+        match self.hostile_enemies().first() {
+            Some(&i) => {
+                let (dx, dy) = ((px - self.entities[i].x).signum(), (py - self.entities[i].y).signum());
+                if (dx, dy) == (0, 0) { (1, 0) } else { (dx, dy) }
+            }
+            None => (1, 0),
+        }
+    }
+
+    /// The tiles a dash toward (tx, ty) crosses, up to `distance`.
+    /// A dash passes through enemies but not walls.
+    pub fn dash_path(&self, tx: i32, ty: i32, distance: i32) -> Vec<(i32, i32)> {
+        line(self.player.x, self.player.y, tx, ty).into_iter()
+            .take(distance as usize)
+            .take_while(|&(x, y)| self.map.in_bounds(x, y) && self.map.tiles[y as usize][x as usize].walkable())
+            .collect()
+    }
+
+    /// Where a dash along `path` sets you down: the furthest tile with nothing standing on it
+    pub fn dash_landing(&self, path: &[(i32, i32)]) -> Option<(i32, i32)> {
+        path.iter().rev().copied()
+            .find(|&(x, y)| self.enemy_at(x, y).is_none() && self.shop_at(x, y).is_none())
     }
 
     fn enemy_turns(&mut self) {
         let px = self.player.x;
         let py = self.player.y;
+        let beacon = self.inventory.wearing_curse(Curse::Beacon) || self.beacon_turns > 0;
+
+        // Open wounds bleed before anything moves
+        for i in 0..self.entities.len() {
+            if self.entities[i].alive && self.entities[i].bleed_turns > 0 {
+                self.entities[i].bleed_turns -= 1;
+                self.hurt_entity(i, 1);
+            }
+        }
 
         for i in 0..self.entities.len() {
             if !self.entities[i].alive || !self.entities[i].kind.is_enemy() {
                 continue;
             }
 
+            // A possessed enemy fights for the player until it shakes loose
+            if self.entities[i].possessed_turns > 0 {
+                self.possessed_turn(i);
+                self.entities[i].possessed_turns -= 1;
+                if self.entities[i].possessed_turns == 0 && self.entities[i].alive {
+                    let name = self.entities[i].kind.name();
+                    self.log(&format!("The {} shakes you loose.", name));
+                }
+                continue;
+            }
+            // A frightened enemy runs from the player
+            if self.entities[i].fear_turns > 0 {
+                self.entities[i].fear_turns -= 1;
+                let (ex, ey) = (self.entities[i].x, self.entities[i].y);
+                self.enemy_chase(i, 2 * ex - px, 2 * ey - py);
+                continue;
+            }
+            let bound = self.entities[i].bound_turns > 0;
+            if bound {
+                self.entities[i].bound_turns -= 1;
+            }
+
             let ex = self.entities[i].x;
             let ey = self.entities[i].y;
 
-            // Only act if visible
-            if !self.map.visible[ey as usize][ex as usize] {
+            // Only act if visible, unless something cursed calls them or she's a Fury
+            let hunting = beacon || self.entities[i].kind == EntityKind::Erinys;
+            if !self.map.visible[ey as usize][ex as usize] && !hunting {
                 continue;
             }
+            if self.map.visible[ey as usize][ex as usize] {
+                self.spotted = true;
+            }
+
+            // Nyx's shadow draws anything nearer to it than to the player
+            if let Some((sx, sy, _)) = self.decoy {
+                let to_shadow = (sx - ex).abs() + (sy - ey).abs();
+                if to_shadow < (px - ex).abs() + (py - ey).abs() {
+                    if to_shadow <= 1 {
+                        self.decoy = None;
+                        let name = self.entities[i].kind.name();
+                        self.log(&format!("The {} tears into your shadow. It comes apart like smoke.", name));
+                    } else if !bound {
+                        self.enemy_chase(i, sx, sy);
+                    }
+                    continue;
+                }
+            }
 
             let dist = ((px - ex).abs() + (py - ey).abs()) as f64;
 
+            let can_shoot = self.entities[i].kind.ranged_range()
+                .is_some_and(|range| dist <= range as f64)
+                && self.clear_shot(i, px, py);
+
             if dist <= 1.5 {
                 // Adjacent: attack player with possible special
                 self.enemy_attack(i);
-            } else if dist < 8.0 {
+            } else if can_shoot && rand::thread_rng().gen_ratio(1, 3) {
+                self.enemy_shoot(i);
+            } else if hunting && !bound {
+                // Hunters know the way, walls or not
+                self.enemy_hunt(i, px, py);
+            } else if dist < 8.0 && !bound {
                 // Chase player
                 self.enemy_chase(i, px, py);
             }
         }
     }
 
+    /// A possessed enemy goes for the nearest of its kin, or keeps close to the player
+    fn possessed_turn(&mut self, i: usize) {
+        let (ex, ey) = (self.entities[i].x, self.entities[i].y);
+        let prey = self.entities.iter().enumerate()
+            .filter(|(j, e)| *j != i && e.alive && e.kind.is_enemy() && e.possessed_turns == 0)
+            .map(|(j, e)| ((e.x - ex).abs().max((e.y - ey).abs()), j))
+            .filter(|&(d, _)| d < 8)
+            .min();
+        match prey {
+            Some((1, j)) => self.ally_strike(i, j),
+            Some((_, j)) => {
+                let (tx, ty) = (self.entities[j].x, self.entities[j].y);
+                self.enemy_chase(i, tx, ty);
+            }
+            None => {
+                let (px, py) = (self.player.x, self.player.y);
+                if (px - ex).abs().max((py - ey).abs()) > 2 {
+                    self.enemy_chase(i, px, py);
+                }
+            }
+        }
+    }
+
+    /// One enemy attacking another on the player's behalf
+    fn ally_strike(&mut self, i: usize, j: usize) {
+        let mut rng = rand::thread_rng();
+        let (acc, eva) = (self.entities[i].accuracy, self.entities[j].evasion);
+        let result = roll_attack(&mut rng, acc, eva);
+        let damage = roll_damage(&mut rng, result, self.entities[i].effective_attack(), self.entities[j].defense);
+        let damage = self.resisted(j, damage, self.entities[i].damage_type);
+        let result = settle(result, damage);
+        let (name, target) = (self.entities[i].kind.name(), self.entities[j].kind.name());
+        if result == HitResult::Miss {
+            self.log(&format!("Your possessed {} lunges at the {} and misses.", name, target));
+        } else {
+            self.log(&format!("Your possessed {} tears into the {} for {} damage.", name, target, damage));
+        }
+        self.hurt_entity(j, damage);
+    }
+
     fn enemy_chase(&mut self, i: usize, px: i32, py: i32) {
         let ex = self.entities[i].x;
         let ey = self.entities[i].y;
@@ -524,63 +2272,143 @@ impl Game {
             if mdx == 0 && mdy == 0 { continue; }
             if !self.map.in_bounds(nx, ny) { continue; }
             if !self.map.tiles[ny as usize][nx as usize].walkable() { continue; }
+            if self.shop_at(nx, ny).is_some() { continue; }
             if self.entities.iter().enumerate().any(|(j, e)| j != i && e.alive && e.kind.is_enemy() && e.x == nx && e.y == ny) {
                 continue;
             }
-            if nx == px && ny == py { continue; }
+            if (nx, ny) == (self.player.x, self.player.y) { continue; }
             self.entities[i].x = nx;
             self.entities[i].y = ny;
             break;
         }
     }
 
+    /// Step along the shortest walk to the player, or chase blindly if that's blocked
+    fn enemy_hunt(&mut self, i: usize, px: i32, py: i32) {
+        let from = (self.entities[i].x, self.entities[i].y);
+        let Some((nx, ny)) = self.map.step_toward(from, (px, py)) else {
+            self.enemy_chase(i, px, py);
+            return;
+        };
+        let blocked = self.shop_at(nx, ny).is_some()
+            || (nx, ny) == (px, py)
+            || self.entities.iter().enumerate().any(|(j, e)| j != i && e.alive && e.kind.is_enemy() && e.x == nx && e.y == ny);
+        if blocked {
+            self.enemy_chase(i, px, py);
+        } else {
+            self.entities[i].x = nx;
+            self.entities[i].y = ny;
+        }
+    }
+
+    /// Whether enemy `i` has an unobstructed line to the player
+    fn clear_shot(&self, i: usize, px: i32, py: i32) -> bool {
+        let (ex, ey) = (self.entities[i].x, self.entities[i].y);
+        line_of_fire(&self.map, ex, ey, px, py)
+            && line(ex, ey, px, py).iter().all(|&(x, y)| {
+                self.enemy_at(x, y).is_none_or(|j| j == i)
+            })
+    }
+
+    fn enemy_shoot(&mut self, idx: usize) {
+        let mut rng = rand::thread_rng();
+        let atk = self.entities[idx].attack - 1;
+        let def = self.player_defense();
+        let acc = self.entities[idx].accuracy - RANGED_PENALTY;
+        let result = roll_attack(&mut rng, acc, self.player_evasion());
+        let damage = roll_damage(&mut rng, result, atk, def);
+        let damage = damage * self.player.kind.resistance(DamageType::Fire) / 100;
+        let name = self.entities[idx].kind.name();
+
+        if result == HitResult::Miss {
+            self.log(&format!("A cinder from the {} hisses past you.", name));
+        } else if damage > 0 {
+            self.log(&format!("The {} hurls a cinder from its torch! {} damage.", name, damage));
+            self.hurt_player(damage, name, DamageType::Fire, "from range");
+        } else {
+            self.log(&format!("The {} hurls a cinder. It gutters out at your feet.", name));
+        }
+    }
+
     fn enemy_attack(&mut self, idx: usize) {
         let mut rng = rand::thread_rng();
         let kind = self.entities[idx].kind;
         let atk = self.entities[idx].attack;
-        let def = self.player.defense;
-        let damage = (atk - def + rng.gen_range(-1..=2)).max(0);
+        let def = self.player_defense();
+        let (acc, eva) = (self.entities[idx].accuracy, self.player_evasion());
+        let chance = connect_chance(acc, eva);
+        let result = roll_attack(&mut rng, acc, eva);
+        let damage = roll_damage(&mut rng, result, atk, def);
+        let damage_type = self.entities[idx].damage_type;
+        let damage = damage * self.player.kind.resistance(damage_type) / 100;
+        let result = settle(result, damage);
         let name = self.entities[idx].kind.name();
 
         // Base attack
+        match result {
+            HitResult::Miss => {
+                self.log(&format!("The {} lunges and misses. ({}%)", name, chance));
+                if self.has_boon(Boon::Riposte) {
+                    self.log(&format!("You answer the opening. The {} takes 3 damage.", name));
+                    self.hurt_entity(idx, 3);
+                }
+                return;
+            }
+            HitResult::Critical => {
+                self.log(&format!("The {} finds a gap in you! {} damage!", name, damage));
+            }
+            HitResult::Glancing => {
+                self.log(&format!("The {} grazes you for {} damage.", name, damage));
+            }
+            HitResult::Hit if damage > 0 => {
+                self.log(&format!("The {} strikes you for {} damage! ({}%)", name, damage, chance));
+            }
+            HitResult::Hit => {
+                self.log(&format!("The {} attacks but you shrug it off.", name));
+            }
+        }
         if damage > 0 {
-            self.player.hp -= damage;
-            self.log(&format!("The {} strikes you for {} damage!", name, damage));
-        } else {
-            self.log(&format!("The {} attacks but you shrug it off.", name));
+            let note = match result {
+                HitResult::Critical => "critical",
+                HitResult::Glancing => "glancing",
+                _ => "",
+            };
+            self.hurt_player(damage, name, damage_type, note);
         }
 
         // Special abilities
         match kind {
-            EntityKind::Lampad => {
-                // Chance to blind
-                if rng.gen_ratio(1, 4) && self.blind_turns == 0 {
-                    self.blind_turns = 5;
-                    self.log("The Lampad's torch flares! You are blinded!");
-                }
+            // Chance to blind
+            EntityKind::Lampad if rng.gen_ratio(1, 4) && self.blind_turns == 0 => {
+                self.blind_turns = 5;
+                self.log("The Lampad's torch flares! You are blinded!");
             }
-            EntityKind::Empusa => {
-                // Life drain
-                if damage > 0 {
-                    let drain = (damage / 2).max(1);
-                    self.entities[idx].hp = (self.entities[idx].hp + drain).min(self.entities[idx].max_hp);
-                    self.log(&format!("The Empusa drains your essence! It heals {}.", drain));
-                }
+            // Life drain
+            _ if damage_type == DamageType::Drain && damage > 0 => {
+                let drain = (damage / 2).max(1);
+                self.entities[idx].hp = (self.entities[idx].hp + drain).min(self.entities[idx].max_hp);
+                self.log(&format!("The {} drains your essence! It heals {}.", name, drain));
             }
-            EntityKind::Eurynomos => {
-                // Chance to reduce defense temporarily
-                if rng.gen_ratio(1, 5) && self.player.defense > 0 {
-                    self.player.defense -= 1;
-                    self.log("The Eurynomos strips your protection! -1 defense.");
-                }
+            // Chance to reduce defense temporarily
+            EntityKind::Eurynomos if rng.gen_ratio(1, 5) && self.player.defense > 0 => {
+                self.player.defense -= 1;
+                self.defense_stripped += 1;
+                self.log("The Eurynomos strips your protection! -1 defense.");
             }
             _ => {}
         }
+    }
+}
 
-        if self.player.hp <= 0 {
-            self.player.alive = false;
-            self.game_over = true;
-            self.killed_by = name.to_string();
-        }
+/// The generator for one floor of a run. The same seed always builds the same floors.
+fn floor_rng(seed: u64, depth: u32) -> StdRng {
+    StdRng::seed_from_u64(seed ^ (depth as u64).wrapping_mul(0x9E37_79B9_7F4A_7C15))
+}
+
+/// Count one more of `kind`
+fn tally(counts: &mut Vec<(EntityKind, u32)>, kind: EntityKind) {
+    match counts.iter_mut().find(|(k, _)| *k == kind) {
+        Some((_, n)) => *n += 1,
+        None => counts.push((kind, 1)),
     }
 }
@@ -0,0 +1,85 @@
+/// Turns on a floor before a hunting Erinys catches the shade's scent
+pub const ERINYS_DELAY: u32 = 40;
+
+/// Opt-in hardships chosen before a run. Each adds heat, and heat multiplies the score.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Heat {
+    /// Monsters have a fifth more HP
+    StubbornDead,
+    /// No shrines on any floor
+    Godless,
+    /// Sight is always cut down to a blinded shade's
+    Lightless,
+    /// Ability cooldowns are doubled
+    Sluggish,
+    /// An Erinys comes for you on every floor
+    Hunted,
+}
+
+impl Heat {
+    pub const ALL: [Heat; 5] = [
+        Heat::StubbornDead,
+        Heat::Godless,
+        Heat::Lightless,
+        Heat::Sluggish,
+        Heat::Hunted,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Heat::StubbornDead => "Stubborn Dead",
+            Heat::Godless => "Godless",
+            Heat::Lightless => "Lightless",
+            Heat::Sluggish => "Sluggish",
+            Heat::Hunted => "Hunted",
+        }
+    }
+
+    pub fn description(self) -> &'static str {
+        match self {
+            Heat::StubbornDead => "Monsters have 20% more HP",
+            Heat::Godless => "No shrines",
+            Heat::Lightless => "You can never see more than 3 tiles",
+            Heat::Sluggish => "Ability cooldowns are doubled",
+            Heat::Hunted => "An Erinys hunts you on every floor",
+        }
+    }
+
+    /// One letter, for the high-score table and the scores file
+    pub fn mark(self) -> char {
+        match self {
+            Heat::StubbornDead => 'D',
+            Heat::Godless => 'G',
+            Heat::Lightless => 'L',
+            Heat::Sluggish => 'S',
+            Heat::Hunted => 'H',
+        }
+    }
+
+    pub fn heat(self) -> u32 {
+        match self {
+            Heat::StubbornDead | Heat::Godless => 1,
+            Heat::Lightless | Heat::Sluggish => 2,
+            Heat::Hunted => 3,
+        }
+    }
+}
+
+pub fn total(active: &[Heat]) -> u32 {
+    active.iter().map(|h| h.heat()).sum()
+}
+
+/// Score multiplier in percent: each point of heat adds a quarter
+pub fn multiplier(active: &[Heat]) -> u32 {
+    100 + 25 * total(active)
+}
+
+/// The active modifiers as letters, e.g. "DGH"
+pub fn marks(active: &[Heat]) -> String {
+    active.iter().map(|h| h.mark()).collect()
+}
+
+/// Read modifiers back from their letters
+pub fn from_marks(marks: &str) -> Vec<Heat> {
+    Heat::ALL.iter().copied().filter(|h| marks.contains(h.mark())).collect()
+}
@@ -0,0 +1,163 @@
+use crate::curse::Curse;
+use crate::entity::EntityKind;
+use crate::equipment::{GearStats, Slot};
+
+/// How many separate stacks a shade can carry
+pub const INVENTORY_CAPACITY: usize = 10;
+
+/// One inventory slot: a kind of item and how many of it
+pub struct ItemStack {
+    pub kind: EntityKind,
+    pub count: u32,
+    pub equipped: bool, // gear only; equipped gear still takes a slot
+    pub curse: Option<Curse>,
+    pub curse_known: bool,
+}
+
+impl ItemStack {
+    /// Whether this stack is equipped and cursed, so it can't come off
+    pub fn stuck(&self) -> bool {
+        self.equipped && self.curse.is_some()
+    }
+}
+
+/// What the player is carrying. Obols go in the purse, not here.
+pub struct Inventory {
+    pub stacks: Vec<ItemStack>,
+    pub capacity: usize,
+}
+
+impl Inventory {
+    pub fn new(capacity: usize) -> Self {
+        Inventory { stacks: Vec::new(), capacity }
+    }
+
+    /// Add one uncursed item, stacking where possible. Returns false if there is no room.
+    pub fn add(&mut self, kind: EntityKind) -> bool {
+        self.add_item(kind, None)
+    }
+
+    pub fn add_item(&mut self, kind: EntityKind, curse: Option<Curse>) -> bool {
+        if kind.stackable() && curse.is_none() {
+            if let Some(stack) = self.stacks.iter_mut().find(|s| s.kind == kind && s.curse.is_none()) {
+                stack.count += 1;
+                return true;
+            }
+        }
+        if self.stacks.len() >= self.capacity {
+            return false;
+        }
+        self.stacks.push(ItemStack { kind, count: 1, equipped: false, curse, curse_known: false });
+        true
+    }
+
+    /// Take one item out of a slot, removing the slot once it is empty
+    pub fn remove_one(&mut self, slot: usize) -> Option<(EntityKind, Option<Curse>)> {
+        let stack = self.stacks.get_mut(slot)?;
+        let (kind, curse) = (stack.kind, stack.curse);
+        stack.count -= 1;
+        if stack.count == 0 {
+            self.stacks.remove(slot);
+        }
+        Some((kind, curse))
+    }
+
+    pub fn get(&self, slot: usize) -> Option<EntityKind> {
+        self.stacks.get(slot).map(|s| s.kind)
+    }
+
+    pub fn find(&self, kind: EntityKind) -> Option<usize> {
+        self.stacks.iter().position(|s| s.kind == kind)
+    }
+
+    /// Index of the stack currently equipped in `slot`
+    pub fn equipped(&self, slot: Slot) -> Option<usize> {
+        self.stacks.iter().position(|s| s.equipped && s.kind.equip_slot() == Some(slot))
+    }
+
+    pub fn equipped_kind(&self, slot: Slot) -> Option<EntityKind> {
+        self.equipped(slot).map(|i| self.stacks[i].kind)
+    }
+
+    /// Summed bonuses from everything equipped
+    pub fn gear_stats(&self) -> GearStats {
+        self.stacks.iter()
+            .filter(|s| s.equipped)
+            .fold(GearStats::default(), |acc, s| {
+                let stats = s.kind.gear_stats();
+                if s.curse == Some(Curse::Leaden) {
+                    acc.plus(stats.negated())
+                } else {
+                    acc.plus(stats)
+                }
+            })
+    }
+
+    /// Whether any equipped gear carries this curse
+    pub fn wearing_curse(&self, curse: Curse) -> bool {
+        self.stacks.iter().any(|s| s.equipped && s.curse == Some(curse))
+    }
+
+    pub fn len(&self) -> usize {
+        self.stacks.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.stacks.is_empty()
+    }
+}
+
+/// Letter shown next to an inventory slot
+pub fn slot_letter(slot: usize) -> char {
+    (b'a' + slot as u8) as char
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn consumables_stack_and_gear_does_not() {
+        let mut inv = Inventory::new(INVENTORY_CAPACITY);
+        assert!(inv.add(EntityKind::Nectar));
+        assert!(inv.add(EntityKind::Nectar));
+        assert!(inv.add(EntityKind::Spear));
+        assert!(inv.add(EntityKind::Spear));
+        assert_eq!(inv.len(), 3);
+        assert_eq!(inv.stacks[0].count, 2);
+    }
+
+    #[test]
+    fn cursed_items_keep_their_own_slot() {
+        let mut inv = Inventory::new(INVENTORY_CAPACITY);
+        inv.add(EntityKind::Nectar);
+        inv.add_item(EntityKind::Nectar, Some(Curse::Withering));
+        inv.add_item(EntityKind::Nectar, Some(Curse::Withering));
+        inv.add(EntityKind::Nectar);
+        assert_eq!(inv.len(), 3);
+        assert_eq!(inv.stacks[0].count, 2);
+    }
+
+    #[test]
+    fn a_full_pack_still_stacks() {
+        let mut inv = Inventory::new(2);
+        inv.add(EntityKind::Nectar);
+        inv.add(EntityKind::Moly);
+        assert!(!inv.add(EntityKind::Pebble));
+        assert!(inv.add(EntityKind::Moly));
+        assert_eq!(inv.stacks[1].count, 2);
+    }
+
+    #[test]
+    fn the_last_one_out_frees_the_slot() {
+        let mut inv = Inventory::new(INVENTORY_CAPACITY);
+        inv.add(EntityKind::Moly);
+        inv.add(EntityKind::Moly);
+        inv.add(EntityKind::Pebble);
+        assert!(inv.remove_one(0).is_some());
+        assert_eq!(inv.len(), 2);
+        assert!(inv.remove_one(0).is_some());
+        assert!(inv.get(0) == Some(EntityKind::Pebble));
+        assert!(inv.remove_one(5).is_none());
+    }
+}
@@ -0,0 +1,121 @@
+use rand::seq::SliceRandom;
+use rand::Rng;
+use crate::ability::{self, AbilityState};
+use crate::entity::EntityKind;
+
+/// Choices laid out at each level-up
+pub const GROWTH_CHOICES: usize = 3;
+
+/// What a shade can take from a level-up
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Growth {
+    Vitality,
+    Might,
+    Guard,
+    Aim,
+    Grace,
+    /// A free rank for the ability in this slot
+    Deepen(usize),
+}
+
+impl Growth {
+    const STATS: [Growth; 5] = [Growth::Vitality, Growth::Might, Growth::Guard, Growth::Aim, Growth::Grace];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Growth::Vitality => "Vitality",
+            Growth::Might => "Might",
+            Growth::Guard => "Guard",
+            Growth::Aim => "Aim",
+            Growth::Grace => "Grace",
+            Growth::Deepen(_) => "Deepen",
+        }
+    }
+
+    pub fn description(self, abilities: &[AbilityState]) -> String {
+        match self {
+            Growth::Vitality => "+8 max HP, and heal 8".to_string(),
+            Growth::Might => "+1 attack".to_string(),
+            Growth::Guard => "+1 defense".to_string(),
+            Growth::Aim => "+5 accuracy".to_string(),
+            Growth::Grace => "+5 evasion".to_string(),
+            Growth::Deepen(slot) => {
+                let ab = &abilities[slot];
+                format!("{} rank {}, no ichor needed", ab.def.name, ability::rank_numeral(ab.rank + 1))
+            }
+        }
+    }
+
+    /// Two stats and, if any ability can still rise, a free rank
+    pub fn offer(abilities: &[AbilityState], rng: &mut impl Rng) -> Vec<Growth> {
+        let risable: Vec<usize> = (0..abilities.len()).filter(|&i| abilities[i].next_effect().is_some()).collect();
+        let stats = if risable.is_empty() { GROWTH_CHOICES } else { GROWTH_CHOICES - 1 };
+        let mut offers: Vec<Growth> = Growth::STATS.choose_multiple(rng, stats).copied().collect();
+        if let Some(&slot) = risable.choose(rng) {
+            offers.push(Growth::Deepen(slot));
+        }
+        offers
+    }
+}
+
+/// Experience for a kill: tougher kinds are worth more, and everything is worth more deeper down
+pub fn xp_for_kill(kind: EntityKind, depth: u32) -> u32 {
+    let base = match kind {
+        EntityKind::LostShade => 3,
+        EntityKind::Lampad => 6,
+        EntityKind::Eurynomos => 10,
+        EntityKind::Empusa => 15,
+        EntityKind::Erinys => 40,
+        _ => 0,
+    };
+    base * (depth + 3) / 4
+}
+
+/// Experience it takes to go from `level` to the next
+pub fn xp_to_next(level: u32) -> u32 {
+    20 * level
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    fn maxed(def: &'static ability::AbilityDef) -> AbilityState {
+        AbilityState { rank: def.ranks.len(), ..AbilityState::new(def) }
+    }
+
+    #[test]
+    fn kills_are_worth_more_deeper() {
+        assert_eq!(xp_for_kill(EntityKind::LostShade, 1), 3);
+        assert_eq!(xp_for_kill(EntityKind::Lampad, 3), 9);
+        assert_eq!(xp_for_kill(EntityKind::Erinys, 5), 80);
+        assert_eq!(xp_for_kill(EntityKind::Nectar, 5), 0);
+    }
+
+    #[test]
+    fn each_level_costs_more() {
+        assert_eq!(xp_to_next(1), 20);
+        assert_eq!(xp_to_next(4), 80);
+    }
+
+    #[test]
+    fn maxed_abilities_are_never_offered() {
+        let abilities = [maxed(&ability::DASH), AbilityState::new(&ability::SPECTRAL_SCREAM), maxed(&ability::LETHES_TOUCH)];
+        for seed in 0..50 {
+            let offers = Growth::offer(&abilities, &mut StdRng::seed_from_u64(seed));
+            assert_eq!(offers.len(), GROWTH_CHOICES);
+            assert!(offers.contains(&Growth::Deepen(1)));
+            assert!(!offers.contains(&Growth::Deepen(0)) && !offers.contains(&Growth::Deepen(2)));
+        }
+    }
+
+    #[test]
+    fn all_maxed_means_only_stats() {
+        let abilities = [maxed(&ability::DASH), maxed(&ability::PHASE)];
+        let offers = Growth::offer(&abilities, &mut StdRng::seed_from_u64(1));
+        assert_eq!(offers.len(), GROWTH_CHOICES);
+        assert!(offers.iter().all(|g| !matches!(g, Growth::Deepen(_))));
+    }
+}
@@ -1,22 +1,49 @@
 mod map;
+mod morgue;
 mod entity;
 mod fov;
 mod game;
+mod heat;
 mod ui;
 mod spawn;
 mod screen;
 mod ability;
+mod achievement;
+mod background;
+mod charon;
+mod inventory;
+mod level;
+mod combat;
+mod curse;
+mod daily;
+mod date;
+mod equipment;
+mod fading;
 mod shrine;
+mod shop;
 mod flavor;
+mod recap;
+mod score;
+mod potion;
+mod profile;
+mod targeting;
 
 use std::io;
+use std::path::PathBuf;
 use crossterm::{
     event::{self, Event, KeyCode, KeyEventKind},
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
     ExecutableCommand,
 };
 use ratatui::prelude::*;
+use background::{Background, FRAGMENT_COUNT};
+use daily::{Daily, DailyHistory};
+use heat::Heat;
 use game::{Game, Action, GameState};
+use profile::{Profile, Unlock};
+use score::{HighScore, HighScores};
+use shrine::Offering;
+use targeting::TargetMode;
 
 fn main() -> io::Result<()> {
     enable_raw_mode()?;
@@ -24,29 +51,181 @@ fn main() -> io::Result<()> {
     let mut terminal = Terminal::new(CrosstermBackend::new(io::stdout()))?;
 
     let mut state = GameState::Title;
+    let mut profile = Profile::load();
+    let mut scores = HighScores::load();
+    let mut history = DailyHistory::load();
+    let mut morgue: Option<PathBuf> = None; // where the last run was written up
+    let mut unsaved: Vec<&'static str> = Vec::new(); // files the last save failed to write
 
     // Pre-create game so we don't need Option
-    let mut game = Game::new();
+    let mut game = Game::new(&profile, 0, Background::Farmer, Vec::new());
+    let mut background = Background::Farmer; // the life chosen for the next run
+    let mut heat: Vec<Heat> = Vec::new();     // and the hardships, kept between runs
 
     loop {
         match state {
             GameState::Title => {
                 terminal.draw(|frame| {
-                    let area = centered_rect(60, 22, frame.area());
-                    frame.render_widget(screen::title_widget(area), area);
+                    let area = centered_rect(72, 30, frame.area());
+                    frame.render_widget(screen::title_widget(&profile, &Daily::today()), area);
+                })?;
+
+                if let Event::Key(key) = event::read()? {
+                    if key.kind == KeyEventKind::Press {
+                        match key.code {
+                            KeyCode::Char('q') | KeyCode::Esc => break,
+                            KeyCode::Char('m') => { state = GameState::Mnemosyne; continue; }
+                            KeyCode::Char('h') => { state = GameState::HighScores; continue; }
+                            KeyCode::Char('d') => { state = GameState::Daily; unsaved.clear(); continue; }
+                            KeyCode::Char('a') => { state = GameState::Achievements; continue; }
+                            KeyCode::Char('c') => { state = GameState::Codex; continue; }
+                            KeyCode::Char('n') | KeyCode::Enter => state = GameState::Background,
+                            _ => {}
+                        }
+                    }
+                }
+            }
+
+            GameState::Background => {
+                terminal.draw(|frame| {
+                    let area = centered_rect(70, 24, frame.area());
+                    frame.render_widget(screen::background_widget(&profile), area);
+                })?;
+
+                if let Event::Key(key) = event::read()? {
+                    if key.kind == KeyEventKind::Press {
+                        match key.code {
+                            KeyCode::Esc => state = GameState::Title,
+                            KeyCode::Char(c) if c.is_ascii_lowercase() => {
+                                let picked = Background::ALL.get((c as u8 - b'a') as usize);
+                                if let Some(&chosen) = picked.filter(|b| b.available(&profile)) {
+                                    background = chosen;
+                                    state = GameState::Heat;
+                                }
+                            }
+                            _ => {}
+                        }
+                    }
+                }
+            }
+
+            GameState::Heat => {
+                terminal.draw(|frame| {
+                    let area = centered_rect(70, 22, frame.area());
+                    frame.render_widget(screen::heat_widget(&heat), area);
+                })?;
+
+                if let Event::Key(key) = event::read()? {
+                    if key.kind == KeyEventKind::Press {
+                        match key.code {
+                            KeyCode::Esc => state = GameState::Background,
+                            KeyCode::Enter => {
+                                game = new_run(&profile, rand::random::<u32>() as u64, background, heat.clone(), None);
+                                state = GameState::Playing;
+                            }
+                            KeyCode::Char(c) if c.is_ascii_lowercase() => {
+                                if let Some(&h) = Heat::ALL.get((c as u8 - b'a') as usize) {
+                                    match heat.iter().position(|&x| x == h) {
+                                        Some(i) => { heat.remove(i); }
+                                        None => heat.push(h),
+                                    }
+                                }
+                            }
+                            _ => {}
+                        }
+                    }
+                }
+            }
+
+            GameState::Daily => {
+                let daily = Daily::today();
+                let played = profile.daily == Some(daily.date.to_string());
+                terminal.draw(|frame| {
+                    let area = centered_rect(76, 28, frame.area());
+                    frame.render_widget(screen::daily_widget(&daily, played, &history, &unsaved), area);
+                })?;
+
+                if let Event::Key(key) = event::read()? {
+                    if key.kind == KeyEventKind::Press {
+                        match key.code {
+                            KeyCode::Esc | KeyCode::Char('d') => state = GameState::Title,
+                            KeyCode::Enter if !played => {
+                                // One attempt a day: it counts from the moment you start,
+                                // so the run doesn't start unless that is written down
+                                let before = profile.daily.replace(daily.date.to_string());
+                                if profile.save().is_err() {
+                                    profile.daily = before;
+                                    unsaved = vec!["your profile"];
+                                } else {
+                                    unsaved.clear();
+                                    game = new_run(&profile, daily.seed, daily.background, Vec::new(), Some(&daily));
+                                    state = GameState::Playing;
+                                }
+                            }
+                            _ => {}
+                        }
+                    }
+                }
+            }
+
+            GameState::Achievements => {
+                terminal.draw(|frame| {
+                    let area = centered_rect(76, 24, frame.area());
+                    frame.render_widget(screen::achievements_widget(&profile), area);
+                })?;
+
+                if let Event::Key(key) = event::read()? {
+                    if key.kind == KeyEventKind::Press {
+                        state = GameState::Title;
+                    }
+                }
+            }
+
+            GameState::Codex => {
+                terminal.draw(|frame| {
+                    let area = centered_rect(76, 40, frame.area());
+                    frame.render_widget(screen::codex_widget(&profile), area);
+                })?;
+
+                if let Event::Key(key) = event::read()? {
+                    if key.kind == KeyEventKind::Press {
+                        state = GameState::Title;
+                    }
+                }
+            }
+
+            GameState::Mnemosyne => {
+                terminal.draw(|frame| {
+                    let area = centered_rect(62, 22, frame.area());
+                    frame.render_widget(screen::mnemosyne_widget(&profile, &unsaved), area);
                 })?;
 
                 if let Event::Key(key) = event::read()? {
                     if key.kind == KeyEventKind::Press {
-                        if key.code == KeyCode::Char('q') || key.code == KeyCode::Esc {
-                            break;
+                        match key.code {
+                            KeyCode::Esc | KeyCode::Char('m') => state = GameState::Title,
+                            KeyCode::Char(c) if c.is_ascii_lowercase() => {
+                                let picked = Unlock::ALL.get((c as u8 - b'a') as usize);
+                                if picked.is_some_and(|u| profile.buy(*u)) {
+                                    unsaved = if profile.save().is_err() { vec!["your profile"] } else { Vec::new() };
+                                }
+                            }
+                            _ => {}
                         }
-                        game = Game::new();
-                        game.log("You awaken in the Asphodel Meadows.");
-                        game.log("Grey flowers stretch endlessly. You remember nothing.");
-                        game.log("You are a shade. You will not last.");
-                        game.log("[1] Dash  [2] Spectral Scream  [3] Lethe's Touch  [e] Shrine");
-                        state = GameState::Playing;
+                    }
+                }
+            }
+
+            GameState::HighScores => {
+                terminal.draw(|frame| {
+                    let area = centered_rect(76, 22, frame.area());
+                    frame.render_widget(screen::high_scores_widget(&scores), area);
+                })?;
+
+                if let Event::Key(key) = event::read()? {
+                    if key.kind == KeyEventKind::Press {
+                        scores.latest = None;
+                        state = GameState::Title;
                     }
                 }
             }
@@ -59,73 +238,210 @@ fn main() -> io::Result<()> {
                         continue;
                     }
 
-                    // Check if at shrine prompt
+                    // A level-up waits on a choice before anything else
+                    if !game.growth_offers.is_empty() {
+                        if let KeyCode::Char(c @ 'a'..='z') = key.code {
+                            game.choose_growth(c as usize - 'a' as usize);
+                        }
+                        continue;
+                    }
+
+                    // Aiming a throw: keys steer the cursor instead of the shade
+                    if game.targeting.is_some() {
+                        match key.code {
+                            KeyCode::Esc => game.cancel_targeting(),
+                            KeyCode::Tab | KeyCode::Char(' ') => game.cycle_target(),
+                            KeyCode::Enter | KeyCode::Char('t') => {
+                                if let Some(action) = game.confirm_target() {
+                                    game.player_action(action);
+                                }
+                            }
+                            code => {
+                                if let Some((dx, dy)) = direction(code) {
+                                    game.move_cursor(dx, dy);
+                                }
+                            }
+                        }
+                        if game.is_over() {
+                            (state, morgue, unsaved) = end_run(&game, &mut profile, &mut scores, &mut history);
+                        }
+                        continue;
+                    }
+
+                    // Inventory screen: pick a slot, then use, drop or throw it
+                    if let Some(slot) = game.inventory_cursor {
+                        match key.code {
+                            KeyCode::Esc | KeyCode::Char('i') => game.inventory_cursor = None,
+                            KeyCode::Down | KeyCode::Char('j') => game.move_inventory_cursor(1),
+                            KeyCode::Up | KeyCode::Char('k') => game.move_inventory_cursor(-1),
+                            KeyCode::Enter | KeyCode::Char('u') => {
+                                game.inventory_cursor = None;
+                                game.player_action(Action::UseItem(slot));
+                            }
+                            KeyCode::Char('w') => {
+                                game.inventory_cursor = None;
+                                game.player_action(Action::Equip(slot));
+                            }
+                            KeyCode::Char('r') => {
+                                game.inventory_cursor = None;
+                                game.player_action(Action::Unequip(slot));
+                            }
+                            KeyCode::Char('d') => {
+                                game.inventory_cursor = None;
+                                game.player_action(Action::DropItem(slot));
+                            }
+                            KeyCode::Char('t') => {
+                                game.inventory_cursor = None;
+                                game.begin_targeting(TargetMode::Throw(slot));
+                            }
+                            _ => {}
+                        }
+                        if game.is_over() {
+                            (state, morgue, unsaved) = end_run(&game, &mut profile, &mut scores, &mut history);
+                        }
+                        continue;
+                    }
+
+                    // Character screen: just a look
+                    if game.character_screen {
+                        if matches!(key.code, KeyCode::Esc | KeyCode::Char('c')) {
+                            game.character_screen = false;
+                        }
+                        continue;
+                    }
+
+                    // Abilities screen: digits spend ichor on ranks
+                    if game.ability_menu {
+                        match key.code {
+                            KeyCode::Esc | KeyCode::Char('a') => game.toggle_ability_menu(),
+                            KeyCode::Char(c @ '1'..='9') => game.upgrade_ability(c as usize - '1' as usize),
+                            _ => {}
+                        }
+                        continue;
+                    }
+
+                    // At the ferry: pay, walk or wait
+                    if game.charon_prompt {
+                        match key.code {
+                            KeyCode::Char('p') => game.pay_charon(),
+                            KeyCode::Char('w') => game.walk_styx_bank(),
+                            KeyCode::Char('s') => game.wait_on_shore(),
+                            KeyCode::Esc => game.leave_charon(),
+                            _ => {}
+                        }
+                        if game.is_over() {
+                            (state, morgue, unsaved) = end_run(&game, &mut profile, &mut scores, &mut history);
+                        }
+                        continue;
+                    }
+
+                    // At Hermes' stall: letters buy, Esc leaves
+                    if game.shop_prompt.is_some() {
+                        match key.code {
+                            KeyCode::Esc => game.leave_shop(),
+                            KeyCode::Char(c) if c.is_ascii_lowercase() => {
+                                game.buy((c as u8 - b'a') as usize);
+                            }
+                            _ => {}
+                        }
+                        continue;
+                    }
+
+                    // At a shrine: letters take a boon, digits make an offering, p strikes a bargain
                     if game.shrine_prompt.is_some() {
                         match key.code {
-                            KeyCode::Char('a') => { game.choose_shrine_boon(true); }
-                            KeyCode::Char('b') => { game.choose_shrine_boon(false); }
-                            KeyCode::Esc => { game.shrine_prompt = None; game.log("You step away from the shrine."); }
+                            KeyCode::Char(c @ 'a'..='c') => game.choose_shrine_boon(c as usize - 'a' as usize),
+                            KeyCode::Char(c @ '1'..='3') => game.make_offering(Offering::ALL[c as usize - '1' as usize]),
+                            KeyCode::Char('p') => game.strike_pact(),
+                            KeyCode::Esc => game.leave_shrine(),
                             _ => {}
                         }
                         continue;
                     }
 
+                    match key.code {
+                        KeyCode::Char('t') => { game.begin_quick_throw(); continue; }
+                        KeyCode::Char('i') => { game.open_inventory(); continue; }
+                        KeyCode::Char('a') => { game.toggle_ability_menu(); continue; }
+                        KeyCode::Char('c') => { game.character_screen = true; continue; }
+                        _ => {}
+                    }
+
                     let action = match key.code {
                         KeyCode::Char('q') | KeyCode::Esc => break,
-                        KeyCode::Up    | KeyCode::Char('k') => Action::Move(0, -1),
-                        KeyCode::Down  | KeyCode::Char('j') => Action::Move(0, 1),
-                        KeyCode::Left  | KeyCode::Char('h') => Action::Move(-1, 0),
-                        KeyCode::Right | KeyCode::Char('l') => Action::Move(1, 0),
-                        KeyCode::Char('y') => Action::Move(-1, -1),
-                        KeyCode::Char('u') => Action::Move(1, -1),
-                        KeyCode::Char('b') => Action::Move(-1, 1),
-                        KeyCode::Char('n') => Action::Move(1, 1),
                         KeyCode::Char('.') | KeyCode::Char('5') => Action::Wait,
                         KeyCode::Char('g') => Action::Pickup,
                         KeyCode::Char('d') => Action::Descend,
                         KeyCode::Char('e') => Action::Interact,
                         KeyCode::Char('x') => Action::Look,
-                        KeyCode::Char('1') => Action::UseAbility(0),
-                        KeyCode::Char('2') => Action::UseAbility(1),
-                        KeyCode::Char('3') => Action::UseAbility(2),
-                        _ => continue,
+                        KeyCode::Char(c @ '1'..='9') => {
+                            let slot = c as usize - '1' as usize;
+                            match game.begin_ability(slot) {
+                                Some(action) => action,
+                                None => continue,
+                            }
+                        }
+                        code => match direction(code) {
+                            Some((dx, dy)) => Action::Move(dx, dy),
+                            None => continue,
+                        },
                     };
                     game.player_action(action);
+                    let earned = profile.learn_achievements(&game.achievements);
+                    if profile.learn_fragments(game.background, game.fragments) || earned {
+                        let _ = profile.save();
+                    }
 
-                    if game.game_over {
-                        state = if game.victory { GameState::Victory } else { GameState::Dead };
+                    if game.is_over() {
+                        (state, morgue, unsaved) = end_run(&game, &mut profile, &mut scores, &mut history);
                     }
                 }
             }
 
             GameState::Dead => {
                 terminal.draw(|frame| {
-                    let area = centered_rect(60, 20, frame.area());
+                    let area = centered_rect(72, 44, frame.area());
+                    let recap = recap::DeathRecap::from_game(&game);
                     frame.render_widget(
-                        screen::death_widget(game.depth, game.obols, game.turns, &game.killed_by),
+                        screen::death_widget(
+                            game.depth,
+                            game.obols,
+                            game.turns,
+                            score::score(&game),
+                            &game.killed_by,
+                            &recap,
+                            &screen::Records { morgue: morgue.as_deref(), unsaved: &unsaved },
+                        ),
                         area,
                     );
                 })?;
 
                 if let Event::Key(key) = event::read()? {
                     if key.kind == KeyEventKind::Press {
-                        break;
+                        state = GameState::HighScores;
                     }
                 }
             }
 
-            GameState::Victory => {
+            GameState::Ended => {
                 terminal.draw(|frame| {
                     let area = centered_rect(60, 22, frame.area());
                     frame.render_widget(
-                        screen::victory_widget(game.obols, game.turns),
+                        screen::ending_widget(
+                            game.ending.unwrap_or(charon::Ending::Elysium),
+                            game.obols,
+                            game.turns,
+                            score::score(&game),
+                            (game.fragments == FRAGMENT_COUNT).then_some(game.background),
+                            &screen::Records { morgue: morgue.as_deref(), unsaved: &unsaved },
+                        ),
                         area,
                     );
                 })?;
 
                 if let Event::Key(key) = event::read()? {
                     if key.kind == KeyEventKind::Press {
-                        break;
+                        state = GameState::HighScores;
                     }
                 }
             }
@@ -137,6 +453,72 @@ fn main() -> io::Result<()> {
     Ok(())
 }
 
+/// A fresh shade, with the opening lines in its log
+fn new_run(profile: &Profile, seed: u64, background: Background, heat: Vec<Heat>, daily: Option<&Daily>) -> Game {
+    // Daily runs start with nothing from the pool, so everyone starts equal
+    let empty = Profile::empty();
+    let gifts = if daily.is_some() { &empty } else { profile };
+    let mut game = Game::new(gifts, seed, background, heat);
+    game.achievements = profile.achievements.clone();
+    if let Some(daily) = daily {
+        daily.apply(&mut game);
+    }
+    game.log("You awaken in the Asphodel Meadows.");
+    game.log("Grey flowers stretch endlessly. You remember nothing.");
+    game.log(background.intro());
+    game.log("You are a shade. You will not last.");
+    let hints: Vec<String> = game.abilities.iter().enumerate()
+        .map(|(i, ab)| format!("[{}] {} — {}", ability::key_for(i), ab.def.name, ab.def.description))
+        .collect();
+    for hint in &hints {
+        game.log(hint);
+    }
+    game.log("[i] Inventory  [a] Abilities  [c] Character  [t] Throw  [x] Look  [e] Shrine");
+    game
+}
+
+/// Bank what the shade carried, record the score, write the morgue file
+/// and pick the end screen. Also returns whatever couldn't be saved.
+fn end_run(
+    game: &Game,
+    profile: &mut Profile,
+    scores: &mut HighScores,
+    history: &mut DailyHistory,
+) -> (GameState, Option<PathBuf>, Vec<&'static str>) {
+    profile.end_run(game.obols);
+    profile.learn_achievements(&game.achievements);
+    profile.learn_fragments(game.background, game.fragments);
+    let mut unsaved = Vec::new();
+    if profile.save().is_err() {
+        unsaved.push("your profile");
+    }
+    scores.record(HighScore::from_game(game));
+    if scores.save().is_err() {
+        unsaved.push("the high scores");
+    }
+    if history.record(game).is_err() {
+        unsaved.push("the daily history");
+    }
+    let morgue = morgue::write(game).ok();
+    let state = if game.ending.is_some() { GameState::Ended } else { GameState::Dead };
+    (state, morgue, unsaved)
+}
+
+/// Map a movement key to a direction
+fn direction(code: KeyCode) -> Option<(i32, i32)> {
+    match code {
+        KeyCode::Up    | KeyCode::Char('k') => Some((0, -1)),
+        KeyCode::Down  | KeyCode::Char('j') => Some((0, 1)),
+        KeyCode::Left  | KeyCode::Char('h') => Some((-1, 0)),
+        KeyCode::Right | KeyCode::Char('l') => Some((1, 0)),
+        KeyCode::Char('y') => Some((-1, -1)),
+        KeyCode::Char('u') => Some((1, -1)),
+        KeyCode::Char('b') => Some((-1, 1)),
+        KeyCode::Char('n') => Some((1, 1)),
+        _ => None,
+    }
+}
+
 /// Create a centered rectangle
 fn centered_rect(width: u16, height: u16, area: Rect) -> Rect {
     let x = area.x + area.width.saturating_sub(width) / 2;
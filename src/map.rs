@@ -1,3 +1,4 @@
+use std::collections::VecDeque;
 use rand::Rng;
 
 pub const MAP_W: usize = 80;
@@ -62,7 +63,7 @@ pub struct Map {
 }
 
 impl Map {
-    pub fn new(depth: u32) -> Self {
+    pub fn new(depth: u32, rng: &mut impl Rng) -> Self {
         let mut map = Map {
             tiles: vec![vec![Tile::Wall; MAP_W]; MAP_H],
             revealed: vec![vec![false; MAP_W]; MAP_H],
@@ -70,12 +71,11 @@ impl Map {
             rooms: Vec::new(),
             depth,
         };
-        map.generate();
+        map.generate(rng);
         map
     }
 
-    fn generate(&mut self) {
-        let mut rng = rand::thread_rng();
+    fn generate(&mut self, rng: &mut impl Rng) {
         let room_count = rng.gen_range(8..16);
 
         for _ in 0..200 {
@@ -92,7 +92,7 @@ impl Map {
                 continue;
             }
 
-            self.carve_room(&room, &mut rng);
+            self.carve_room(&room, rng);
             if let Some(prev) = self.rooms.last() {
                 let (cx, cy) = room.center();
                 let (px, py) = prev.center();
@@ -129,10 +129,8 @@ impl Map {
     fn carve_h_tunnel(&mut self, x1: usize, x2: usize, y: usize) {
         let (start, end) = if x1 < x2 { (x1, x2) } else { (x2, x1) };
         for x in start..=end {
-            if y < MAP_H && x < MAP_W {
-                if self.tiles[y][x] == Tile::Wall {
-                    self.tiles[y][x] = Tile::Floor;
-                }
+            if y < MAP_H && x < MAP_W && self.tiles[y][x] == Tile::Wall {
+                self.tiles[y][x] = Tile::Floor;
             }
         }
     }
@@ -140,10 +138,8 @@ impl Map {
     fn carve_v_tunnel(&mut self, y1: usize, y2: usize, x: usize) {
         let (start, end) = if y1 < y2 { (y1, y2) } else { (y2, y1) };
         for y in start..=end {
-            if y < MAP_H && x < MAP_W {
-                if self.tiles[y][x] == Tile::Wall {
-                    self.tiles[y][x] = Tile::Floor;
-                }
+            if y < MAP_H && x < MAP_W && self.tiles[y][x] == Tile::Wall {
+                self.tiles[y][x] = Tile::Floor;
             }
         }
     }
@@ -152,6 +148,36 @@ impl Map {
         x >= 0 && y >= 0 && (x as usize) < MAP_W && (y as usize) < MAP_H
     }
 
+    /// The first step of a shortest walk from `from` to `to`, going round walls.
+    /// None if `to` can't be reached, or `from` is already there.
+    pub fn step_toward(&self, from: (i32, i32), to: (i32, i32)) -> Option<(i32, i32)> {
+        let mut came_from = vec![[None; MAP_W]; MAP_H];
+        let mut queue = VecDeque::from([from]);
+        came_from[from.1 as usize][from.0 as usize] = Some(from);
+        while let Some((x, y)) = queue.pop_front() {
+            if (x, y) == to {
+                // Walk back until the tile just after the start
+                let mut step = to;
+                while let Some(prev) = came_from[step.1 as usize][step.0 as usize].filter(|&p| p != from) {
+                    step = prev;
+                }
+                return (step != from).then_some(step);
+            }
+            for (dx, dy) in [(1, 0), (-1, 0), (0, 1), (0, -1), (1, 1), (1, -1), (-1, 1), (-1, -1)] {
+                let (nx, ny) = (x + dx, y + dy);
+                if !self.in_bounds(nx, ny)
+                    || came_from[ny as usize][nx as usize].is_some()
+                    || !self.tiles[ny as usize][nx as usize].walkable()
+                {
+                    continue;
+                }
+                came_from[ny as usize][nx as usize] = Some((x, y));
+                queue.push_back((nx, ny));
+            }
+        }
+        None
+    }
+
     pub fn clear_visible(&mut self) {
         for row in &mut self.visible {
             for v in row.iter_mut() {
@@ -0,0 +1,174 @@
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+use crate::ability;
+use crate::background::FRAGMENT_COUNT;
+use crate::date::{self, Date};
+use crate::equipment::Slot;
+use crate::game::Game;
+use crate::heat;
+use crate::map::{MAP_H, MAP_W};
+use crate::profile::data_dir;
+use crate::score;
+use crate::shop::HERMES_GLYPH;
+
+/// Write a plain-text account of the run to the morgue directory, and say where it went
+pub fn write(game: &Game) -> io::Result<PathBuf> {
+    let dir = data_dir().join("morgue");
+    fs::create_dir_all(&dir)?;
+    // Same-seed runs on the same day (every daily, every replay) each keep their own file
+    let path = dir.join(format!("morgue-{}-{}-{}.txt", Date::today(), date::time_of_day(), game.seed));
+    fs::write(&path, dump(game))?;
+    Ok(path)
+}
+
+/// Everything worth remembering about a run, for sharing
+fn dump(game: &Game) -> String {
+    let mut out: Vec<String> = Vec::new();
+    let p = &game.player;
+
+    out.push("Asphodel morgue file".to_string());
+    out.push("====================".to_string());
+    out.push(format!("Date: {}   Seed: {}", Date::today(), game.seed));
+    if let Some(date) = game.daily {
+        out.push(format!("Daily challenge for {}", date));
+    }
+    out.push(String::new());
+    out.push(format!("Once a {}.", game.background.name().to_lowercase()));
+    out.push(format!("{} on floor {} after {} turns.", score::cause(game), game.depth, game.turns));
+    out.push(format!("Score: {}", score::score(game)));
+    if !game.heat.is_empty() {
+        let names: Vec<&str> = game.heat.iter().map(|h| h.name()).collect();
+        out.push(format!(
+            "Heat {} ({}%): {}",
+            heat::total(&game.heat), heat::multiplier(&game.heat), names.join(", "),
+        ));
+    }
+
+    out.push(String::new());
+    out.push("Stats".to_string());
+    out.push(format!(
+        "  HP {}/{}   ATK {}   DEF {}   ACC {}   EVA {}   Sight {}",
+        p.hp, p.max_hp, game.player_attack(), game.player_defense(),
+        game.player_accuracy(), game.player_evasion(), game.effective_fov(),
+    ));
+    out.push(format!("  Level {}   Obols {}   Ichor {}   Kills {}", game.level, game.obols, game.ichor, game.kill_count()));
+    out.push(format!(
+        "  Fading {} ({})   Memories {}/{}",
+        game.fading, game.fading_stage().name().to_lowercase(), game.fragments, FRAGMENT_COUNT,
+    ));
+
+    out.push(String::new());
+    out.push("Abilities".to_string());
+    for (i, ab) in game.abilities.iter().enumerate() {
+        out.push(format!(
+            "  [{}] {} {} — {}",
+            ability::key_for(i), ab.def.name, ability::rank_numeral(ab.rank), ab.effect().summary(),
+        ));
+    }
+
+    out.push(String::new());
+    out.push("Boons".to_string());
+    if game.boons.is_empty() {
+        out.push("  None. The gods never noticed.".to_string());
+    }
+    for boon in &game.boons {
+        out.push(format!(
+            "  {:<20} {:<10} {:<8} {}",
+            boon.name(), boon.god().name(), boon.rarity().name(), boon.description(),
+        ));
+    }
+
+    out.push(String::new());
+    out.push("Equipped".to_string());
+    for slot in [Slot::Weapon, Slot::Armour, Slot::Trinket] {
+        let worn = game.inventory.equipped(slot)
+            .map_or("—".to_string(), |i| game.stack_name(&game.inventory.stacks[i]));
+        out.push(format!("  {:<11} {}", slot.marker(), worn));
+    }
+
+    out.push(String::new());
+    out.push("Pack".to_string());
+    if game.inventory.stacks.is_empty() {
+        out.push("  Empty.".to_string());
+    }
+    for stack in &game.inventory.stacks {
+        out.push(format!("  {} x{}", game.stack_name(stack), stack.count));
+    }
+
+    out.push(String::new());
+    out.push("Items found".to_string());
+    if game.found.is_empty() {
+        out.push("  Nothing.".to_string());
+    }
+    for (kind, count) in &game.found {
+        out.push(format!("  {:<24} {}", game.item_name(*kind), count));
+    }
+
+    out.push(String::new());
+    out.push("Kills".to_string());
+    if game.kills.is_empty() {
+        out.push("  None.".to_string());
+    }
+    for (kind, count) in &game.kills {
+        out.push(format!("  {:<24} {}", kind.name(), count));
+    }
+
+    out.push(String::new());
+    out.push("Last messages".to_string());
+    for msg in &game.log {
+        out.push(format!("  {}", msg));
+    }
+
+    out.push(String::new());
+    out.push(format!("Floor {}, as far as it was seen", game.depth));
+    for row in map_rows(game) {
+        out.push(format!("  {}", row));
+    }
+    out.join("\n") + "\n"
+}
+
+/// The final floor in text: revealed tiles, with what's standing on the visible ones
+fn map_rows(game: &Game) -> Vec<String> {
+    let map = &game.map;
+    let mut rows: Vec<String> = (0..MAP_H).map(|y| {
+        let row: String = (0..MAP_W).map(|x| {
+            let (mx, my) = (x as i32, y as i32);
+            if !map.revealed[y][x] {
+                return ' ';
+            }
+            if game.player.x == mx && game.player.y == my {
+                return '@';
+            }
+            if game.shrines.iter().any(|s| s.x == mx && s.y == my) {
+                return 'Ω';
+            }
+            if game.shop_at(mx, my).is_some() {
+                return HERMES_GLYPH;
+            }
+            if game.fonts.iter().any(|f| f.x == mx && f.y == my) {
+                return '≈';
+            }
+            if map.visible[y][x] {
+                if let Some(ent) = game.entities.iter().find(|e| e.alive && e.x == mx && e.y == my) {
+                    return ent.kind.glyph();
+                }
+            }
+            map.tiles[y][x].glyph()
+        }).collect();
+        row.trim_end().to_string()
+    }).collect();
+
+    // Drop the unexplored rows above and below, and the empty margin on the left
+    while rows.last().is_some_and(|r| r.is_empty()) {
+        rows.pop();
+    }
+    let first = rows.iter().position(|r| !r.is_empty()).unwrap_or(rows.len());
+    let rows = rows.split_off(first);
+    let margin = rows.iter()
+        .filter(|r| !r.is_empty())
+        .map(|r| r.chars().take_while(|c| *c == ' ').count())
+        .min()
+        .unwrap_or(0);
+    rows.iter().map(|r| r.chars().skip(margin).collect()).collect()
+}
@@ -0,0 +1,114 @@
+use rand::seq::SliceRandom;
+use rand::Rng;
+
+/// What a potion actually does, once you drink it
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum PotionEffect {
+    /// Heal 20 HP
+    Healing,
+    /// 2 damage a turn for 5 turns
+    Poison,
+    /// The world slows; enemies act every other turn for 10 turns
+    Haste,
+    /// Blinded for 8 turns
+    Blindness,
+    /// Forget the floor you have explored
+    Amnesia,
+    /// The whole floor laid bare
+    SeeAll,
+}
+
+impl PotionEffect {
+    pub const ALL: [PotionEffect; 6] = [
+        PotionEffect::Healing,
+        PotionEffect::Poison,
+        PotionEffect::Haste,
+        PotionEffect::Blindness,
+        PotionEffect::Amnesia,
+        PotionEffect::SeeAll,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            PotionEffect::Healing => "Draught of Healing",
+            PotionEffect::Poison => "Draught of Poison",
+            PotionEffect::Haste => "Draught of Haste",
+            PotionEffect::Blindness => "Draught of Blindness",
+            PotionEffect::Amnesia => "Water of Lethe",
+            PotionEffect::SeeAll => "Draught of Far Sight",
+        }
+    }
+
+    pub fn description(self) -> &'static str {
+        match self {
+            PotionEffect::Healing => "Heals 20 HP",
+            PotionEffect::Poison => "2 damage a turn for 5 turns",
+            PotionEffect::Haste => "Enemies act half as often for 10 turns",
+            PotionEffect::Blindness => "Blinds you for 8 turns",
+            PotionEffect::Amnesia => "Forget the floor you've explored",
+            PotionEffect::SeeAll => "Reveals the whole floor",
+        }
+    }
+
+    pub fn random(rng: &mut impl Rng) -> Self {
+        PotionEffect::ALL[rng.gen_range(0..PotionEffect::ALL.len())]
+    }
+}
+
+/// Every run shuffles these across the potion effects
+const APPEARANCES: [&str; 10] = [
+    "murky vial",
+    "silver flask",
+    "cracked amphora",
+    "bone phial",
+    "smoking cup",
+    "black bottle",
+    "weeping gourd",
+    "amber ampoule",
+    "clay lekythos",
+    "glass tear",
+];
+
+/// Which potion looks like what this run, and which the shade has learned
+pub struct PotionBook {
+    appearances: Vec<(PotionEffect, &'static str)>,
+    known: Vec<PotionEffect>,
+}
+
+impl PotionBook {
+    pub fn new(rng: &mut impl Rng) -> Self {
+        let mut looks = APPEARANCES.to_vec();
+        looks.shuffle(rng);
+        let appearances = PotionEffect::ALL.iter().copied().zip(looks).collect();
+        PotionBook { appearances, known: Vec::new() }
+    }
+
+    pub fn appearance(&self, effect: PotionEffect) -> &'static str {
+        self.appearances.iter()
+            .find(|(e, _)| *e == effect)
+            .map_or("strange vial", |(_, look)| look)
+    }
+
+    pub fn is_known(&self, effect: PotionEffect) -> bool {
+        self.known.contains(&effect)
+    }
+
+    /// Learn an effect. Returns true if it was new.
+    pub fn learn(&mut self, effect: PotionEffect) -> bool {
+        if self.is_known(effect) {
+            false
+        } else {
+            self.known.push(effect);
+            true
+        }
+    }
+
+    /// The name the player sees: the true name if known, else how it looks
+    pub fn display_name(&self, effect: PotionEffect) -> String {
+        if self.is_known(effect) {
+            effect.name().to_string()
+        } else {
+            self.appearance(effect).to_string()
+        }
+    }
+}
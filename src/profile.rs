@@ -0,0 +1,213 @@
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+use crate::achievement::Achievement;
+use crate::background::Background;
+
+/// Permanent gifts bought at the Pool of Mnemosyne between runs
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Unlock {
+    /// More max HP at the start of every run
+    Vigor,
+    /// More attack at the start of every run
+    Strength,
+    /// Start every run with a fourth ability from the echoes' pool
+    FourthGift,
+    /// The priestess background can be chosen
+    Priestess,
+    /// The thief background can be chosen
+    Thief,
+}
+
+impl Unlock {
+    pub const ALL: [Unlock; 5] = [
+        Unlock::Vigor,
+        Unlock::Strength,
+        Unlock::FourthGift,
+        Unlock::Priestess,
+        Unlock::Thief,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Unlock::Vigor => "Remembered Vigor",
+            Unlock::Strength => "Remembered Strength",
+            Unlock::FourthGift => "A Fourth Gift",
+            Unlock::Priestess => "The Priestess",
+            Unlock::Thief => "The Thief",
+        }
+    }
+
+    pub fn description(self) -> &'static str {
+        match self {
+            Unlock::Vigor => "+5 starting max HP per rank",
+            Unlock::Strength => "+1 starting attack per rank",
+            Unlock::FourthGift => "Start with a fourth ability, learned from an echo",
+            Unlock::Priestess => "Remember a life at Hecate's crossroads: a new background",
+            Unlock::Thief => "Remember a life of other people's purses: a new background",
+        }
+    }
+
+    /// Name in the profile file
+    fn key(self) -> &'static str {
+        match self {
+            Unlock::Vigor => "vigor",
+            Unlock::Strength => "strength",
+            Unlock::FourthGift => "fourth_gift",
+            Unlock::Priestess => "priestess",
+            Unlock::Thief => "thief",
+        }
+    }
+
+    pub fn max_rank(self) -> u32 {
+        match self {
+            Unlock::Vigor => 3,
+            Unlock::Strength => 2,
+            Unlock::FourthGift | Unlock::Priestess | Unlock::Thief => 1,
+        }
+    }
+
+    /// Banked obols it takes to go from `rank` to the next
+    pub fn cost(self, rank: u32) -> u32 {
+        match self {
+            Unlock::Vigor => 5 * (rank + 1),
+            Unlock::Strength => 8 * (rank + 1),
+            Unlock::FourthGift => 20,
+            Unlock::Priestess | Unlock::Thief => 12,
+        }
+    }
+}
+
+/// Where saved files live: $ASPHODEL_HOME, or ~/.asphodel
+pub fn data_dir() -> PathBuf {
+    if let Ok(dir) = std::env::var("ASPHODEL_HOME") {
+        return PathBuf::from(dir);
+    }
+    match std::env::var("HOME") {
+        Ok(home) => PathBuf::from(home).join(".asphodel"),
+        Err(_) => PathBuf::from(".asphodel"),
+    }
+}
+
+/// What carries over from one run to the next
+pub struct Profile {
+    pub banked: u32, // obols laid at the Pool of Mnemosyne, not yet spent
+    pub runs: u32,
+    pub daily: Option<String>, // date of the last daily challenge attempted
+    pub achievements: Vec<Achievement>,
+    pub codex: Vec<(Background, usize)>, // memory fragments ever found, by life and place in it
+    ranks: Vec<(Unlock, u32)>,
+}
+
+impl Profile {
+    fn path() -> PathBuf {
+        data_dir().join("profile.txt")
+    }
+
+    /// A shade with nothing remembered. Daily challenges are played as one, so
+    /// everyone starts equal.
+    pub fn empty() -> Self {
+        Profile { banked: 0, runs: 0, daily: None, achievements: Vec::new(), codex: Vec::new(), ranks: Vec::new() }
+    }
+
+    /// Read the profile from disk. A missing or unreadable file is a fresh start.
+    pub fn load() -> Self {
+        let mut profile = Profile::empty();
+        let Ok(text) = fs::read_to_string(Self::path()) else { return profile };
+        for line in text.lines() {
+            let Some((key, value)) = line.split_once('=') else { continue };
+            match key.trim() {
+                "daily" => {
+                    profile.daily = Some(value.trim().to_string());
+                    continue;
+                }
+                "achievements" => {
+                    profile.achievements = value.split(',').filter_map(|k| Achievement::from_key(k.trim())).collect();
+                    continue;
+                }
+                "codex" => {
+                    profile.codex = value.split(',').filter_map(|entry| {
+                        let (life, index) = entry.trim().split_once(':')?;
+                        Some((Background::from_key(life)?, index.parse().ok()?))
+                    }).collect();
+                    continue;
+                }
+                _ => {}
+            }
+            let Ok(value) = value.trim().parse::<u32>() else { continue };
+            match key.trim() {
+                "banked" => profile.banked = value,
+                "runs" => profile.runs = value,
+                key => {
+                    if let Some(unlock) = Unlock::ALL.iter().find(|u| u.key() == key) {
+                        profile.ranks.push((*unlock, value.min(unlock.max_rank())));
+                    }
+                }
+            }
+        }
+        profile
+    }
+
+    pub fn save(&self) -> io::Result<()> {
+        let mut text = format!("banked={}\nruns={}\n", self.banked, self.runs);
+        if let Some(date) = &self.daily {
+            text.push_str(&format!("daily={}\n", date));
+        }
+        let achievements: Vec<&str> = self.achievements.iter().map(|a| a.key()).collect();
+        text.push_str(&format!("achievements={}\n", achievements.join(",")));
+        let codex: Vec<String> = self.codex.iter().map(|(b, i)| format!("{}:{}", b.key(), i)).collect();
+        text.push_str(&format!("codex={}\n", codex.join(",")));
+        for (unlock, rank) in &self.ranks {
+            text.push_str(&format!("{}={}\n", unlock.key(), rank));
+        }
+        fs::create_dir_all(data_dir())?;
+        fs::write(Self::path(), text)
+    }
+
+    pub fn rank(&self, unlock: Unlock) -> u32 {
+        self.ranks.iter().find(|(u, _)| *u == unlock).map_or(0, |(_, r)| *r)
+    }
+
+    /// Take in achievements earned during a run. Returns true if any were new.
+    pub fn learn_achievements(&mut self, earned: &[Achievement]) -> bool {
+        let before = self.achievements.len();
+        for a in earned {
+            if !self.achievements.contains(a) {
+                self.achievements.push(*a);
+            }
+        }
+        self.achievements.len() > before
+    }
+
+    /// Write the first `found` fragments of a life into the codex. Returns true if any were new.
+    pub fn learn_fragments(&mut self, background: Background, found: usize) -> bool {
+        let before = self.codex.len();
+        for i in 0..found {
+            if !self.codex.contains(&(background, i)) {
+                self.codex.push((background, i));
+            }
+        }
+        self.codex.len() > before
+    }
+
+    /// Bank what the shade carried at the end of a run
+    pub fn end_run(&mut self, obols: u32) {
+        self.banked += obols;
+        self.runs += 1;
+    }
+
+    /// Spend banked obols on the next rank of an unlock. Returns false if it can't be bought.
+    pub fn buy(&mut self, unlock: Unlock) -> bool {
+        let rank = self.rank(unlock);
+        let cost = unlock.cost(rank);
+        if rank >= unlock.max_rank() || self.banked < cost {
+            return false;
+        }
+        self.banked -= cost;
+        match self.ranks.iter_mut().find(|(u, _)| *u == unlock) {
+            Some(entry) => entry.1 += 1,
+            None => self.ranks.push((unlock, 1)),
+        }
+        true
+    }
+}
@@ -0,0 +1,113 @@
+use crate::combat::DamageType;
+use crate::curse::Curse;
+use crate::game::Game;
+
+/// How many damage events the recap remembers
+pub const RECAP_EVENTS: usize = 10;
+/// How many turns of HP history the recap remembers
+pub const RECAP_TURNS: usize = 20;
+
+/// One blow that landed on the player
+pub struct DamageEvent {
+    pub turn: u32,
+    pub source: String,
+    pub amount: i32,
+    pub damage_type: DamageType,
+    pub note: &'static str, // "critical", "from range", ... or empty
+}
+
+/// Everything the death screen needs to explain how the shade came apart
+pub struct DeathRecap {
+    pub events: Vec<String>,
+    pub hp_history: Vec<i32>,
+    pub max_hp: i32,
+    pub effects: Vec<String>,
+    pub notes: Vec<String>,
+}
+
+impl DeathRecap {
+    pub fn from_game(game: &Game) -> Self {
+        let events = game.damage_events.iter().map(|ev| {
+            let note = if ev.note.is_empty() { String::new() } else { format!(", {}", ev.note) };
+            format!("T{:<4} {:<12} -{:<3} ({}{})", ev.turn, ev.source, ev.amount, ev.damage_type.name(), note)
+        }).collect();
+
+        let mut effects = Vec::new();
+        if game.blind_turns > 0 {
+            effects.push(format!("Blinded ({} turns left)", game.blind_turns));
+        }
+        if game.player.strength_turns > 0 {
+            effects.push(format!("Moly +4 attack ({} turns left)", game.player.strength_turns));
+        }
+        if game.poison_turns > 0 {
+            effects.push(format!("Poisoned ({} turns left)", game.poison_turns));
+        }
+        if game.haste_turns > 0 {
+            effects.push(format!("Hasted ({} turns left)", game.haste_turns));
+        }
+        if game.wither_turns > 0 {
+            effects.push(format!("Withering curse ({} turns left)", game.wither_turns));
+        }
+        if game.beacon_turns > 0 {
+            effects.push(format!("Beacon curse ({} turns left)", game.beacon_turns));
+        }
+        if game.defense_stripped > 0 {
+            effects.push(format!("Defense stripped -{}", game.defense_stripped));
+        }
+        for stack in game.inventory.stacks.iter().filter(|s| s.equipped) {
+            if let Some(curse) = stack.curse {
+                effects.push(format!("Cursed {} ({})", stack.kind.name(), curse.name()));
+            }
+        }
+
+        let mut notes = Vec::new();
+        if game.blind_turns > 0 {
+            notes.push("You died blind: your sight shrank to 3 tiles and your blows landed less often.".to_string());
+        }
+        if game.defense_stripped > 0 {
+            notes.push(format!(
+                "Eurynomos had torn away {} defense; you died with {} of your armour left.",
+                game.defense_stripped, game.player_defense(),
+            ));
+        }
+        if game.inventory.wearing_curse(Curse::Withering) {
+            notes.push(format!("Withering gear held you to {} of your {} HP.", game.hp_cap(), game.player.max_hp));
+        }
+        if game.wither_turns > 0 && !game.inventory.wearing_curse(Curse::Withering) {
+            notes.push(format!("A withering curse held you to {} of your {} HP.", game.hp_cap(), game.player.max_hp));
+        }
+        if game.damage_events.iter().any(|ev| ev.note == "critical") {
+            notes.push("A critical hit ignored your defense entirely.".to_string());
+        }
+        if game.damage_events.iter().any(|ev| ev.damage_type == DamageType::Drain) {
+            notes.push("Every drain you suffered healed the thing that did it.".to_string());
+        }
+        if game.damage_events.iter().any(|ev| ev.note == "poison") {
+            notes.push("Poison kept working on you between blows.".to_string());
+        }
+        if game.damage_events.iter().any(|ev| ev.note == "from range") {
+            notes.push("Some of it came from beyond arm's reach.".to_string());
+        }
+
+        DeathRecap {
+            events,
+            hp_history: game.hp_history.clone(),
+            max_hp: game.player.max_hp,
+            effects,
+            notes,
+        }
+    }
+
+    /// HP over the final turns as a row of block characters
+    pub fn hp_sparkline(&self) -> String {
+        const BARS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+        self.hp_history.iter().map(|&hp| {
+            if hp <= 0 {
+                '·'
+            } else {
+                let level = (hp * 7 / self.max_hp.max(1)).clamp(0, 7) as usize;
+                BARS[level]
+            }
+        }).collect()
+    }
+}
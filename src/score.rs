@@ -0,0 +1,181 @@
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+use crate::charon::Ending;
+use crate::date::Date;
+use crate::game::Game;
+use crate::heat::{self, Heat};
+use crate::profile::data_dir;
+
+/// How many runs the high-score table keeps
+pub const MAX_SCORES: usize = 10;
+
+/// Points for a finished run: 100 a floor, 10 a kill, 5 an obol still held,
+/// a bonus for getting across, and a point lost for every 20 turns taken.
+/// Heat multiplies the lot.
+pub fn score(game: &Game) -> u32 {
+    let ending = match game.ending {
+        Some(ending) if ending.is_victory() => 1000,
+        _ => 0,
+    };
+    let earned = game.depth * 100 + game.kill_count() * 10 + game.obols * 5 + ending;
+    earned.saturating_sub(game.turns / 20) * heat::multiplier(&game.heat) / 100
+}
+
+/// How the run ended, in a few words
+pub fn cause(game: &Game) -> String {
+    match game.ending {
+        Some(Ending::Elysium) => "Ferried to Elysium".to_string(),
+        Some(Ending::StyxBank) => "Crossed the Styx on foot".to_string(),
+        Some(Ending::Shore) => "Waits on the shore".to_string(),
+        None => format!("Killed by {}", game.killed_by),
+    }
+}
+
+pub struct HighScore {
+    pub score: u32,
+    pub date: String,
+    pub seed: u64,
+    pub depth: u32,
+    pub heat: Vec<Heat>,
+    pub cause: String,
+}
+
+impl HighScore {
+    pub fn from_game(game: &Game) -> Self {
+        HighScore {
+            score: score(game),
+            date: Date::today().to_string(),
+            seed: game.seed,
+            depth: game.depth,
+            heat: game.heat.clone(),
+            cause: cause(game),
+        }
+    }
+
+    /// One tab-separated line of the scores file
+    fn to_line(&self) -> String {
+        format!(
+            "{}\t{}\t{}\t{}\t{}\t{}",
+            self.score, self.date, self.seed, self.depth, heat::marks(&self.heat), self.cause,
+        )
+    }
+
+    /// Lines written before heat existed have no marks field
+    fn from_line(line: &str) -> Option<Self> {
+        let fields: Vec<&str> = line.splitn(6, '\t').collect();
+        let (marks, cause) = match fields.len() {
+            6 => (fields[4], fields[5]),
+            5 => ("", fields[4]),
+            _ => return None,
+        };
+        Some(HighScore {
+            score: fields[0].parse().ok()?,
+            date: fields[1].to_string(),
+            seed: fields[2].parse().ok()?,
+            depth: fields[3].parse().ok()?,
+            heat: heat::from_marks(marks),
+            cause: cause.to_string(),
+        })
+    }
+}
+
+/// The best runs on this machine, best first
+pub struct HighScores {
+    pub entries: Vec<HighScore>,
+    pub latest: Option<usize>, // where the run just finished landed, if it made the table
+}
+
+impl HighScores {
+    fn path() -> PathBuf {
+        data_dir().join("scores.txt")
+    }
+
+    pub fn load() -> Self {
+        let entries = fs::read_to_string(Self::path())
+            .map(|text| text.lines().filter_map(HighScore::from_line).take(MAX_SCORES).collect())
+            .unwrap_or_default();
+        HighScores { entries, latest: None }
+    }
+
+    pub fn save(&self) -> io::Result<()> {
+        let text: String = self.entries.iter().map(|e| e.to_line() + "\n").collect();
+        fs::create_dir_all(data_dir())?;
+        fs::write(Self::path(), text)
+    }
+
+    /// Add a finished run to the table, keeping only the best
+    pub fn record(&mut self, entry: HighScore) {
+        let rank = self.entries.iter().position(|e| e.score < entry.score).unwrap_or(self.entries.len());
+        self.entries.insert(rank, entry);
+        self.entries.truncate(MAX_SCORES);
+        self.latest = (rank < MAX_SCORES).then_some(rank);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::background::Background;
+    use crate::profile::Profile;
+
+    fn game(heat: Vec<Heat>) -> Game {
+        Game::new(&Profile::empty(), 7, Background::Farmer, heat)
+    }
+
+    #[test]
+    fn score_counts_floors_kills_obols_and_ending() {
+        let mut g = game(Vec::new());
+        assert_eq!(score(&g), 100);
+        g.depth = 3;
+        g.obols = 4;
+        g.ending = Some(Ending::StyxBank);
+        assert_eq!(score(&g), 300 + 20 + 1000);
+        g.ending = Some(Ending::Shore);
+        assert_eq!(score(&g), 300 + 20);
+        g.turns = 200;
+        assert_eq!(score(&g), 320 - 10);
+    }
+
+    #[test]
+    fn heat_multiplies_the_score() {
+        // Hunted is 3 heat: 175%
+        assert_eq!(score(&game(vec![Heat::Hunted])), 175);
+    }
+
+    #[test]
+    fn line_round_trip() {
+        let entry = HighScore {
+            score: 1234,
+            date: "2024-02-29".to_string(),
+            seed: 42,
+            depth: 5,
+            heat: vec![Heat::Godless, Heat::Hunted],
+            cause: "Killed by Empusa".to_string(),
+        };
+        let line = entry.to_line();
+        assert_eq!(line, "1234\t2024-02-29\t42\t5\tGH\tKilled by Empusa");
+        let back = HighScore::from_line(&line).unwrap();
+        assert_eq!(back.score, 1234);
+        assert_eq!(back.date, "2024-02-29");
+        assert_eq!(back.seed, 42);
+        assert_eq!(back.depth, 5);
+        assert!(back.heat == vec![Heat::Godless, Heat::Hunted]);
+        assert_eq!(back.cause, "Killed by Empusa");
+    }
+
+    #[test]
+    fn old_lines_have_no_heat() {
+        let back = HighScore::from_line("900\t2024-01-01\t3\t7\tFerried to Elysium").unwrap();
+        assert_eq!((back.score, back.seed, back.depth), (900, 3, 7));
+        assert!(back.heat.is_empty());
+        assert_eq!(back.cause, "Ferried to Elysium");
+    }
+
+    #[test]
+    fn bad_lines_are_skipped() {
+        assert!(HighScore::from_line("").is_none());
+        assert!(HighScore::from_line("x\t2024-01-01\t3\t7\tcause").is_none());
+        assert!(HighScore::from_line("1\t2\t3").is_none());
+    }
+}
@@ -1,5 +1,15 @@
+use std::path::Path;
 use ratatui::prelude::*;
-use ratatui::widgets::Paragraph;
+use ratatui::widgets::{Paragraph, Wrap};
+use crate::ability;
+use crate::achievement::Achievement;
+use crate::background::{Background, FRAGMENT_COUNT};
+use crate::charon::Ending;
+use crate::daily::{Daily, DailyHistory};
+use crate::heat::{self, Heat};
+use crate::profile::{data_dir, Profile, Unlock};
+use crate::recap::DeathRecap;
+use crate::score::HighScores;
 
 const TITLE_ART: &str = r#"
 
@@ -18,8 +28,6 @@ const TITLE_ART: &str = r#"
 
                      But you can try.
 
-
-                 ✿  Press any key  ✿
 "#;
 
 const DEATH_ART: &str = r#"
@@ -51,8 +59,40 @@ const VICTORY_ART: &str = r#"
 
 "#;
 
-pub fn title_widget(_area: Rect) -> Paragraph<'static> {
-    let lines: Vec<Line> = TITLE_ART
+const STYX_ART: &str = r#"
+
+                    ~  ~  ~  ~  ~  ~  ~
+
+
+                You walked the bank of the Styx.
+
+            No coin, no ferry. Just your feet,
+            and the long way round the black water.
+
+               On the far side, something like light.
+               It lets you in. It doesn't ask.
+
+
+"#;
+
+const SHORE_ART: &str = r#"
+
+                    ·  ·  ·  ~  ·  ·  ·
+
+
+                You sat down on the shore.
+
+            The skiff went out and came back
+            and went out again. Never for you.
+
+               A hundred years. The sand is soft.
+               You have stopped counting.
+
+
+"#;
+
+pub fn title_widget(profile: &Profile, daily: &Daily) -> Paragraph<'static> {
+    let mut lines: Vec<Line> = TITLE_ART
         .lines()
         .map(|l| {
             Line::from(Span::styled(
@@ -62,10 +102,301 @@ pub fn title_widget(_area: Rect) -> Paragraph<'static> {
         })
         .collect();
 
+    let key = Style::default().fg(Color::Rgb(200, 180, 120)).bold();
+    let text = Style::default().fg(Color::Rgb(140, 130, 100));
+    let menu = [
+        ("n", "Begin a new run"),
+        ("d", "Today's challenge"),
+        ("m", "The Pool of Mnemosyne"),
+        ("h", "High scores"),
+        ("a", "Achievements"),
+        ("c", "The codex of memories"),
+        ("q", "Quit"),
+    ];
+    for (k, label) in menu {
+        lines.push(Line::from(vec![
+            Span::styled(format!("                 [{}] ", k), key),
+            Span::styled(label, text),
+        ]));
+    }
+    let played = profile.daily == Some(daily.date.to_string());
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled(
+        format!(
+            "      Today ({}): the {}{}",
+            daily.date,
+            daily.background.name().to_lowercase(),
+            if played { ", already walked" } else { "" },
+        ),
+        Style::default().fg(Color::Rgb(120, 140, 160)),
+    )));
+    lines.push(Line::from(Span::styled(
+        format!("      Omens: {}", daily.omens_text()),
+        Style::default().fg(Color::Rgb(120, 140, 160)),
+    )));
+    if profile.runs > 0 {
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled(
+            format!("          Runs: {}  |  Obols banked at the pool: {}", profile.runs, profile.banked),
+            Style::default().fg(Color::Rgb(100, 100, 100)),
+        )));
+    }
+
+    Paragraph::new(lines).alignment(Alignment::Left)
+}
+
+/// Before a run: who were you?
+pub fn background_widget(profile: &Profile) -> Paragraph<'static> {
+    let heading = Style::default().fg(Color::Rgb(200, 180, 120)).bold();
+    let text = Style::default().fg(Color::Rgb(170, 160, 130));
+    let dim = Style::default().fg(Color::Rgb(80, 80, 80));
+
+    let mut lines = vec![
+        Line::from(""),
+        Line::from(Span::styled("            ✿  Who were you, before?  ✿", heading)),
+        Line::from(""),
+    ];
+
+    for (i, background) in Background::ALL.iter().enumerate() {
+        let open = background.available(profile);
+        let style = if open { text } else { dim };
+        let (slot, def) = background.ability_swap();
+        lines.push(Line::from(Span::styled(
+            format!("   {}) {}", (b'a' + i as u8) as char, background.name()),
+            if open { heading } else { dim },
+        )));
+        if !open {
+            lines.push(Line::from(Span::styled("        Forgotten. Remember it at the Pool of Mnemosyne.", dim)));
+            lines.push(Line::from(""));
+            continue;
+        }
+        lines.push(Line::from(Span::styled(format!("        {}", background.description()), style)));
+        lines.push(Line::from(Span::styled(
+            format!("        {}; {} in place of {}", background.stats_text(), def.name, ability::STARTING[slot].name),
+            dim.fg(Color::Rgb(120, 115, 100)),
+        )));
+        lines.push(Line::from(""));
+    }
+
+    lines.push(Line::from(Span::styled(
+        "                [a-d] choose  [Esc] back",
+        Style::default().fg(Color::DarkGray),
+    )));
+
+    Paragraph::new(lines).alignment(Alignment::Left)
+}
+
+/// Before a run: which hardships to take on, for a better score
+pub fn heat_widget(active: &[Heat]) -> Paragraph<'static> {
+    let heading = Style::default().fg(Color::Rgb(200, 90, 70)).bold();
+    let on = Style::default().fg(Color::Rgb(220, 140, 110));
+    let off = Style::default().fg(Color::Rgb(110, 105, 100));
+    let dim = Style::default().fg(Color::Rgb(80, 80, 80));
+
+    let mut lines = vec![
+        Line::from(""),
+        Line::from(Span::styled("          ✿  What will you bear?  ✿", heading)),
+        Line::from(""),
+        Line::from(Span::styled("   The Furies keep a ledger. The more you ask of yourself,", dim)),
+        Line::from(Span::styled("   the more the run is worth.", dim)),
+        Line::from(""),
+    ];
+    for (i, h) in Heat::ALL.iter().enumerate() {
+        let chosen = active.contains(h);
+        lines.push(Line::from(Span::styled(
+            format!(
+                "   {}) [{}] {:<14} heat {}  {}",
+                (b'a' + i as u8) as char,
+                if chosen { 'x' } else { ' ' },
+                h.name(),
+                h.heat(),
+                h.description(),
+            ),
+            if chosen { on } else { off },
+        )));
+    }
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled(
+        format!("   Heat {}: scores count for {}%", heat::total(active), heat::multiplier(active)),
+        heading,
+    )));
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled(
+        "          [a-e] toggle  [Enter] begin  [Esc] back",
+        Style::default().fg(Color::DarkGray),
+    )));
+
+    Paragraph::new(lines).alignment(Alignment::Left)
+}
+
+/// The day's challenge, and how the past ones went
+pub fn daily_widget(daily: &Daily, played: bool, history: &DailyHistory, unsaved: &[&str]) -> Paragraph<'static> {
+    let heading = Style::default().fg(Color::Rgb(150, 170, 200)).bold();
+    let text = Style::default().fg(Color::Rgb(170, 170, 180));
+    let dim = Style::default().fg(Color::Rgb(90, 90, 100));
+
+    let mut lines = vec![
+        Line::from(""),
+        Line::from(Span::styled(format!("      ~  The meadows of {}  ~", daily.date), heading)),
+        Line::from(""),
+        Line::from(Span::styled("   The same floors for every shade today, and one try each.", dim)),
+        Line::from(Span::styled("   Nothing from the Pool of Mnemosyne comes with you.", dim)),
+        Line::from(""),
+        Line::from(Span::styled(format!("   Seed {}   Life: {}", daily.seed, daily.background.name()), text)),
+    ];
+    for omen in daily.omens {
+        lines.push(Line::from(Span::styled(format!("   Omen: {} — {}", omen.name(), omen.description()), text)));
+    }
+
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled("   Past days", heading)));
+    if history.results.is_empty() {
+        lines.push(Line::from(Span::styled("   None yet.", dim)));
+    }
+    for result in history.results.iter().rev().take(10) {
+        lines.push(Line::from(Span::styled(
+            format!(
+                "   {}  {:<9} {:>6}  depth {}  {}",
+                result.date, result.background, result.score, result.depth, result.cause,
+            ),
+            text,
+        )));
+    }
+
+    lines.push(Line::from(""));
+    lines.extend(unsaved_line(unsaved));
+    let hint = if played {
+        "          You've walked today's meadows. Come back tomorrow.  [Esc] back"
+    } else {
+        "                     [Enter] begin  [Esc] back"
+    };
+    lines.push(Line::from(Span::styled(hint, Style::default().fg(Color::DarkGray))));
+
+    Paragraph::new(lines).alignment(Alignment::Left)
+}
+
+/// Every achievement, earned or not
+pub fn achievements_widget(profile: &Profile) -> Paragraph<'static> {
+    let heading = Style::default().fg(Color::Rgb(200, 180, 100)).bold();
+    let earned = Style::default().fg(Color::Rgb(200, 180, 100));
+    let text = Style::default().fg(Color::Rgb(150, 145, 130));
+    let dim = Style::default().fg(Color::Rgb(80, 80, 80));
+
+    let mut lines = vec![
+        Line::from(""),
+        Line::from(Span::styled(
+            format!(
+                "            ✿  Deeds ({}/{})  ✿",
+                profile.achievements.len(),
+                Achievement::ALL.len(),
+            ),
+            heading,
+        )),
+        Line::from(""),
+    ];
+    for a in Achievement::ALL {
+        let done = profile.achievements.contains(&a);
+        lines.push(Line::from(vec![
+            Span::styled(format!("   {} ", if done { '★' } else { '·' }), if done { earned } else { dim }),
+            Span::styled(format!("{:<20}", a.name()), if done { earned } else { text }),
+            Span::styled(a.description(), if done { text } else { dim }),
+        ]));
+    }
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled(
+        "                     [Press any key to go back]",
+        Style::default().fg(Color::DarkGray),
+    )));
+
+    Paragraph::new(lines).alignment(Alignment::Left)
+}
+
+/// Every fragment of every life found so far, across all runs
+pub fn codex_widget(profile: &Profile) -> Paragraph<'static> {
+    let heading = Style::default().fg(Color::Rgb(220, 180, 200)).bold();
+    let text = Style::default().fg(Color::Rgb(170, 160, 150));
+    let dim = Style::default().fg(Color::Rgb(80, 80, 80));
+
+    let mut lines = vec![
+        Line::from(""),
+        Line::from(Span::styled(
+            format!(
+                "            §  What you remember ({}/{})  §",
+                profile.codex.len(),
+                Background::ALL.len() * FRAGMENT_COUNT,
+            ),
+            heading,
+        )),
+    ];
+    for background in Background::ALL {
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled(format!("   Once a {}", background.name().to_lowercase()), heading)));
+        for (i, fragment) in background.fragments().into_iter().enumerate() {
+            lines.push(if profile.codex.contains(&(background, i)) {
+                Line::from(Span::styled(format!("     {}", fragment), text))
+            } else {
+                Line::from(Span::styled("     · · ·", dim))
+            });
+        }
+    }
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled(
+        "                     [Press any key to go back]",
+        Style::default().fg(Color::DarkGray),
+    )));
+
+    Paragraph::new(lines).alignment(Alignment::Left)
+}
+
+/// Between runs: spend banked obols on gifts that last
+pub fn mnemosyne_widget(profile: &Profile, unsaved: &[&str]) -> Paragraph<'static> {
+    let heading = Style::default().fg(Color::Rgb(130, 160, 200)).bold();
+    let text = Style::default().fg(Color::Rgb(180, 180, 190));
+    let dim = Style::default().fg(Color::Rgb(90, 90, 100));
+
+    let mut lines = vec![
+        Line::from(""),
+        Line::from(Span::styled("      ~  The Pool of Mnemosyne  ~", heading)),
+        Line::from(""),
+        Line::from(Span::styled("   Lethe makes the dead forget. Her sister's pool gives", dim)),
+        Line::from(Span::styled("   something back, for a price. Every obol you die holding", dim)),
+        Line::from(Span::styled("   sinks here.", dim)),
+        Line::from(""),
+        Line::from(Span::styled(format!("   Obols banked: {}", profile.banked), text)),
+        Line::from(""),
+    ];
+
+    for (i, unlock) in Unlock::ALL.iter().enumerate() {
+        let rank = profile.rank(*unlock);
+        let maxed = rank >= unlock.max_rank();
+        let cost = if maxed { "owned".to_string() } else { format!("{} obols", unlock.cost(rank)) };
+        let style = if maxed || profile.banked < unlock.cost(rank) { dim } else { text };
+        lines.push(Line::from(Span::styled(
+            format!("   {}) {:<22} {}/{}  {:>9}", (b'a' + i as u8) as char, unlock.name(), rank, unlock.max_rank(), cost),
+            style,
+        )));
+        lines.push(Line::from(Span::styled(format!("        {}", unlock.description()), dim)));
+    }
+
+    lines.push(Line::from(""));
+    lines.extend(unsaved_line(unsaved));
+    lines.push(Line::from(Span::styled(
+        "          [a-e] drink  [Esc] back to the meadows",
+        Style::default().fg(Color::DarkGray),
+    )));
+
     Paragraph::new(lines).alignment(Alignment::Left)
 }
 
-pub fn death_widget(depth: u32, obols: u32, turns: u32, killed_by: &str) -> Paragraph<'static> {
+pub fn death_widget(
+    depth: u32,
+    obols: u32,
+    turns: u32,
+    score: u32,
+    killed_by: &str,
+    recap: &DeathRecap,
+    records: &Records,
+) -> Paragraph<'static> {
     let mut lines: Vec<Line> = DEATH_ART
         .lines()
         .map(|l| {
@@ -79,30 +410,97 @@ pub fn death_widget(depth: u32, obols: u32, turns: u32, killed_by: &str) -> Para
     let stats_color = Color::Rgb(100, 100, 100);
     lines.push(Line::from(""));
     lines.push(Line::from(Span::styled(
-        format!("              Depth: {}  |  Obols: {}  |  Turns: {}", depth, obols, turns),
+        format!("         Depth: {}  |  Obols: {}  |  Turns: {}  |  Score: {}", depth, obols, turns, score),
         Style::default().fg(stats_color),
     )));
     lines.push(Line::from(Span::styled(
         format!("              Killed by: {}", killed_by),
         Style::default().fg(stats_color),
     )));
+
+    // Recap: what hit you, how fast you fell, what was wrong with you
+    let heading = Style::default().fg(Color::Rgb(140, 100, 100));
+    let detail = Style::default().fg(Color::Rgb(110, 105, 100));
     lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled("      The last blows:", heading)));
+    for ev in &recap.events {
+        lines.push(Line::from(Span::styled(format!("        {}", ev), detail)));
+    }
     lines.push(Line::from(""));
     lines.push(Line::from(Span::styled(
-        "                     [Press any key to quit]",
+        format!("      HP over the final {} turns:", recap.hp_history.len()),
+        heading,
+    )));
+    lines.push(Line::from(Span::styled(
+        format!("        {}", recap.hp_sparkline()),
+        Style::default().fg(Color::Rgb(160, 80, 80)),
+    )));
+    lines.push(Line::from(""));
+    let effects = if recap.effects.is_empty() { "none".to_string() } else { recap.effects.join(", ") };
+    lines.push(Line::from(vec![
+        Span::styled("      Afflictions: ", heading),
+        Span::styled(effects, detail),
+    ]));
+    for note in &recap.notes {
+        lines.push(Line::from(Span::styled(format!("        · {}", note), detail)));
+    }
+    lines.push(Line::from(""));
+    lines.push(morgue_line(records.morgue));
+    lines.extend(unsaved_line(records.unsaved));
+    lines.push(Line::from(Span::styled(
+        "                     [Press any key to continue]",
         Style::default().fg(Color::DarkGray),
     )));
 
-    Paragraph::new(lines).alignment(Alignment::Left)
+    Paragraph::new(lines).alignment(Alignment::Left).wrap(Wrap { trim: false })
 }
 
-pub fn victory_widget(obols: u32, turns: u32) -> Paragraph<'static> {
-    let mut lines: Vec<Line> = VICTORY_ART
+pub fn ending_widget(
+    ending: Ending,
+    obols: u32,
+    turns: u32,
+    score: u32,
+    remembered: Option<Background>,
+    records: &Records,
+) -> Paragraph<'static> {
+    let (art, color, closing): (&str, Color, [&str; 3]) = match ending {
+        Ending::Elysium => (VICTORY_ART, Color::Rgb(200, 180, 100), [
+            "                 ☀  You were nobody special.  ☀",
+            "                 ☀  You are nobody special.   ☀",
+            "                 ☀  But you made it anyway.   ☀",
+        ]),
+        Ending::StyxBank => (STYX_ART, Color::Rgb(120, 150, 170), [
+            "                 ~  You couldn't pay.           ~",
+            "                 ~  Nobody carried you.         ~",
+            "                 ~  You crossed anyway.         ~",
+        ]),
+        Ending::Shore => (SHORE_ART, Color::Rgb(110, 110, 130), [
+            "                 ·  You were nobody special.  ·",
+            "                 ·  Charon doesn't do favours. ·",
+            "                 ·  The shore keeps you.       ·",
+        ]),
+    };
+
+    // A shade that found every piece of its life goes out under its own name
+    let closing = match remembered {
+        Some(background) => {
+            let mark = match ending {
+                Ending::Elysium => '☀',
+                Ending::StyxBank => '~',
+                Ending::Shore => '·',
+            };
+            let [who, what] = background.remembered_ending();
+            [who, what, "You are somebody. You always were."].map(|line| format!("                 {}  {}", mark, line))
+        }
+        None => closing.map(str::to_string),
+    };
+
+    let mut lines: Vec<Line> = art
         .lines()
         .map(|l| {
             Line::from(Span::styled(
                 l.to_string(),
-                Style::default().fg(Color::Rgb(200, 180, 100)),
+                Style::default().fg(color),
             ))
         })
         .collect();
@@ -110,26 +508,96 @@ pub fn victory_widget(obols: u32, turns: u32) -> Paragraph<'static> {
     let stats_color = Color::Rgb(160, 150, 100);
     lines.push(Line::from(""));
     lines.push(Line::from(Span::styled(
-        format!("              Obols: {}  |  Turns: {}", obols, turns),
+        format!("              Obols: {}  |  Turns: {}  |  Score: {}", obols, turns, score),
         Style::default().fg(stats_color),
     )));
     lines.push(Line::from(""));
+    for line in closing {
+        lines.push(Line::from(Span::styled(
+            line,
+            Style::default().fg(color).bold(),
+        )));
+    }
+    lines.push(Line::from(""));
+    lines.push(morgue_line(records.morgue));
+    lines.extend(unsaved_line(records.unsaved));
     lines.push(Line::from(Span::styled(
-        "                 ☀  You were nobody special.  ☀",
-        Style::default().fg(Color::Rgb(200, 180, 100)).bold(),
+        "                     [Press any key to continue]",
+        Style::default().fg(Color::DarkGray),
     )));
+
+    Paragraph::new(lines).alignment(Alignment::Left)
+}
+
+/// Where the run was written up, for the end screens
+/// Where a finished run was written down, and what couldn't be saved
+pub struct Records<'a> {
+    pub morgue: Option<&'a Path>,
+    pub unsaved: &'a [&'a str],
+}
+
+fn morgue_line(morgue: Option<&Path>) -> Line<'static> {
+    let text = match morgue {
+        Some(path) => format!("      Morgue file: {}", path.display()),
+        None => "      The morgue file couldn't be written.".to_string(),
+    };
+    Line::from(Span::styled(text, Style::default().fg(Color::Rgb(90, 90, 90))))
+}
+
+/// A warning when something couldn't be written to disk
+fn unsaved_line(unsaved: &[&str]) -> Option<Line<'static>> {
+    if unsaved.is_empty() {
+        return None;
+    }
+    Some(Line::from(Span::styled(
+        format!("      Couldn't save {}. Check {}.", unsaved.join(", "), data_dir().display()),
+        Style::default().fg(Color::Rgb(200, 90, 80)),
+    )))
+}
+
+/// The best runs so far, with the one just finished picked out
+pub fn high_scores_widget(scores: &HighScores) -> Paragraph<'static> {
+    let heading = Style::default().fg(Color::Rgb(200, 180, 100)).bold();
+    let text = Style::default().fg(Color::Rgb(160, 150, 120));
+    let dim = Style::default().fg(Color::Rgb(90, 90, 90));
+
+    let mut lines = vec![
+        Line::from(""),
+        Line::from(Span::styled("                      ✿  The Remembered  ✿", heading)),
+        Line::from(""),
+        Line::from(Span::styled(
+            format!("  {:>2}  {:>6}  {:<10}  {:>10}  {:>5}  {:<5}  {}", "#", "Score", "Date", "Seed", "Depth", "Heat", "End"),
+            dim,
+        )),
+    ];
+
+    if scores.entries.is_empty() {
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled("      No shade has been remembered yet.", text)));
+    }
+    for (i, entry) in scores.entries.iter().enumerate() {
+        let style = if scores.latest == Some(i) { heading } else { text };
+        lines.push(Line::from(Span::styled(
+            format!(
+                "  {:>2}  {:>6}  {:<10}  {:>10}  {:>5}  {:<5}  {}",
+                i + 1, entry.score, entry.date, entry.seed, entry.depth, heat::marks(&entry.heat), entry.cause,
+            ),
+            style,
+        )));
+    }
+
+    lines.push(Line::from(""));
     lines.push(Line::from(Span::styled(
-        "                 ☀  You are nobody special.   ☀",
-        Style::default().fg(Color::Rgb(200, 180, 100)).bold(),
+        "  Score: 100 a floor, 10 a kill, 5 an obol kept, +1000 for crossing,",
+        dim,
     )));
     lines.push(Line::from(Span::styled(
-        "                 ☀  But you made it anyway.   ☀",
-        Style::default().fg(Color::Rgb(200, 180, 100)).bold(),
+        "  +250 for waiting on the shore, -1 every 20 turns; each point of heat adds 25%.",
+        dim,
     )));
     lines.push(Line::from(""));
-    lines.push(Line::from(""));
     lines.push(Line::from(Span::styled(
-        "                     [Press any key to quit]",
+        "                     [Press any key to continue]",
         Style::default().fg(Color::DarkGray),
     )));
 
@@ -0,0 +1,76 @@
+use rand::Rng;
+use crate::entity::EntityKind;
+use crate::potion::PotionEffect;
+use crate::spawn::pick_gear;
+
+/// Something Hermes will part with, for a price
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Ware {
+    Item(EntityKind),
+    /// Restore the shade to full HP
+    Mending,
+    /// Ask the gods on this floor's shrine for different boons
+    BoonReroll,
+    /// Lift the curses from everything carried
+    Uncursing,
+}
+
+impl Ware {
+    /// Price on the first floor; deeper floors cost more
+    fn base_price(self) -> u32 {
+        match self {
+            Ware::Item(kind) if kind.equip_slot().is_some() => 4,
+            Ware::Item(EntityKind::Pebble) => 1,
+            Ware::Item(_) => 2,
+            Ware::Mending => 3,
+            Ware::BoonReroll => 2,
+            Ware::Uncursing => 3,
+        }
+    }
+}
+
+pub struct ShopOffer {
+    pub ware: Ware,
+    pub price: u32,
+    pub sold: bool,
+}
+
+/// Hermes' stall. He only visits some floors.
+pub struct Shop {
+    pub x: i32,
+    pub y: i32,
+    pub offers: Vec<ShopOffer>,
+}
+
+impl Shop {
+    pub fn new(x: i32, y: i32, depth: u32, rng: &mut impl Rng) -> Self {
+        let mut wares = Vec::new();
+        for _ in 0..4 {
+            let kind = match rng.gen_range(0..6) {
+                0 => EntityKind::Nectar,
+                1 => EntityKind::Moly,
+                2 => EntityKind::Pebble,
+                3 => EntityKind::Potion(PotionEffect::random(rng)),
+                4 => EntityKind::SibylLeaf,
+                _ => pick_gear(rng),
+            };
+            wares.push(Ware::Item(kind));
+        }
+        wares.push(Ware::Mending);
+        wares.push(Ware::BoonReroll);
+        wares.push(Ware::Uncursing);
+
+        let offers = wares.into_iter()
+            .map(|ware| ShopOffer { ware, price: price_at(ware.base_price(), depth), sold: false })
+            .collect();
+        Shop { x, y, offers }
+    }
+}
+
+/// Prices climb by a quarter for every floor below the first
+pub fn price_at(base: u32, depth: u32) -> u32 {
+    (base as f32 * (1.0 + 0.25 * (depth as f32 - 1.0))).round() as u32
+}
+
+/// Hermes' glyph on the map
+pub const HERMES_GLYPH: char = '☿';
@@ -1,59 +1,373 @@
 use rand::Rng;
+use ratatui::style::Color;
+
+/// The gods who keep shrines in the underworld. Each shrine belongs to one.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum God {
+    Hades,
+    Persephone,
+    Nyx,
+    Hermes,
+    Ares,
+    Athena,
+    Hecate,
+}
+
+impl God {
+    pub const ALL: [God; 7] = [
+        God::Hades,
+        God::Persephone,
+        God::Nyx,
+        God::Hermes,
+        God::Ares,
+        God::Athena,
+        God::Hecate,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            God::Hades => "Hades",
+            God::Persephone => "Persephone",
+            God::Nyx => "Nyx",
+            God::Hermes => "Hermes",
+            God::Ares => "Ares",
+            God::Athena => "Athena",
+            God::Hecate => "Hecate",
+        }
+    }
+
+    /// How the shrine looks, for the log
+    pub fn shrine_text(self) -> &'static str {
+        match self {
+            God::Hades => "A shrine of black basalt, cold to the touch.",
+            God::Persephone => "A shrine wound with pomegranate vines.",
+            God::Nyx => "A shrine that swallows the light around it.",
+            God::Hermes => "A shrine of worn milestones, heaped with coins.",
+            God::Ares => "A shrine of rusted shields, still warm.",
+            God::Athena => "A shrine of white marble. An owl watches.",
+            God::Hecate => "A shrine at a crossroads, three torches burning.",
+        }
+    }
+
+    pub fn color(self) -> Color {
+        match self {
+            God::Hades => Color::Rgb(140, 110, 180),
+            God::Persephone => Color::Rgb(200, 110, 140),
+            God::Nyx => Color::Rgb(90, 90, 150),
+            God::Hermes => Color::Rgb(220, 200, 120),
+            God::Ares => Color::Rgb(200, 70, 60),
+            God::Athena => Color::Rgb(180, 190, 210),
+            God::Hecate => Color::Rgb(230, 140, 60),
+        }
+    }
+
+    pub fn random(rng: &mut impl Rng) -> Self {
+        God::ALL[rng.gen_range(0..God::ALL.len())]
+    }
+}
+
+/// How rarely a boon turns up at its god's shrine
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Rarity {
+    Common,
+    Rare,
+    Epic,
+    /// A pact struck at a shrine. Never drawn at random.
+    Bargain,
+}
+
+impl Rarity {
+    pub fn name(self) -> &'static str {
+        match self {
+            Rarity::Common => "Common",
+            Rarity::Rare => "Rare",
+            Rarity::Epic => "Epic",
+            Rarity::Bargain => "Bargain",
+        }
+    }
+
+    pub fn color(self) -> Color {
+        match self {
+            Rarity::Common => Color::Rgb(180, 170, 160),
+            Rarity::Rare => Color::Rgb(110, 160, 230),
+            Rarity::Epic => Color::Rgb(200, 120, 230),
+            Rarity::Bargain => Color::Rgb(200, 70, 60),
+        }
+    }
+
+    /// Relative chance of being drawn
+    fn weight(self) -> u32 {
+        match self {
+            Rarity::Common => 60,
+            Rarity::Rare => 30,
+            Rarity::Epic => 10,
+            Rarity::Bargain => 0,
+        }
+    }
+}
 
 /// Shrine boons — the player picks one when they interact
 #[derive(Clone, Copy, PartialEq, Eq)]
 pub enum Boon {
+    // Hades
     /// +10 max HP and heal to full
     VitalityOfErebus,
-    /// +3 attack
-    WrathOfAres,
-    /// +2 defense
-    AegisOfAthena,
-    /// Reduce all ability cooldowns by 3
-    SwiftnessOfHermes,
+    /// Kills heal 2 HP
+    HadesTithe,
+    /// Slain enemies sometimes leave an obol
+    WealthOfPlouton,
+    // Persephone
     /// +15 HP heal
     GraceOfPersephone,
+    /// Lift the curses from everything carried
+    MercyOfPersephone,
+    /// Each descent heals 10 more
+    SpringReturns,
+    // Nyx
     /// See further (FOV +3)
     EyesOfNyx,
+    /// +15 evasion
+    VeilOfNyx,
+    /// Dash leaves a shadow behind for enemies to chase
+    ShadowDecoys,
+    // Hermes
+    /// Reduce all ability cooldowns by 3
+    SwiftnessOfHermes,
+    /// +3 obols
+    PurseOfHermes,
+    /// Ability cooldowns a quarter shorter
+    Quicksilver,
+    // Ares
+    /// +3 attack
+    WrathOfAres,
+    /// Kills grant a few turns of strength
+    Bloodlust,
+    /// +5 attack
+    SpearOfAres,
+    // Athena
+    /// +2 defense
+    AegisOfAthena,
+    /// +10 accuracy
+    OwlSight,
+    /// Enemies that miss you in melee take damage
+    Riposte,
+    // Hecate
+    /// +3 ichor
+    CrossroadsToll,
+    /// Raise a random ability by one rank
+    FavourOfHecate,
+    /// Your physical blows burn
+    TorchesOfHecate,
+    // Bargains: strong, with a price that lasts
+    /// +5 attack, but monsters move faster
+    PactOfAres,
+    /// +20 max HP, but descending no longer heals
+    PactOfHades,
+    /// +25 evasion, but you see less
+    PactOfNyx,
+    /// Every ability gains a rank, but each use costs HP
+    PactOfHecate,
 }
 
 impl Boon {
+    pub const ALL: [Boon; 21] = [
+        Boon::VitalityOfErebus,
+        Boon::HadesTithe,
+        Boon::WealthOfPlouton,
+        Boon::GraceOfPersephone,
+        Boon::MercyOfPersephone,
+        Boon::SpringReturns,
+        Boon::EyesOfNyx,
+        Boon::VeilOfNyx,
+        Boon::ShadowDecoys,
+        Boon::SwiftnessOfHermes,
+        Boon::PurseOfHermes,
+        Boon::Quicksilver,
+        Boon::WrathOfAres,
+        Boon::Bloodlust,
+        Boon::SpearOfAres,
+        Boon::AegisOfAthena,
+        Boon::OwlSight,
+        Boon::Riposte,
+        Boon::CrossroadsToll,
+        Boon::FavourOfHecate,
+        Boon::TorchesOfHecate,
+    ];
+
     pub fn name(self) -> &'static str {
         match self {
             Boon::VitalityOfErebus => "Vitality of Erebus",
-            Boon::WrathOfAres => "Wrath of Ares",
-            Boon::AegisOfAthena => "Aegis of Athena",
-            Boon::SwiftnessOfHermes => "Swiftness of Hermes",
+            Boon::HadesTithe => "Hades' Tithe",
+            Boon::WealthOfPlouton => "Wealth of Plouton",
             Boon::GraceOfPersephone => "Grace of Persephone",
+            Boon::MercyOfPersephone => "Mercy of Persephone",
+            Boon::SpringReturns => "Spring Returns",
             Boon::EyesOfNyx => "Eyes of Nyx",
+            Boon::VeilOfNyx => "Veil of Nyx",
+            Boon::ShadowDecoys => "Shadow Decoys",
+            Boon::SwiftnessOfHermes => "Swiftness of Hermes",
+            Boon::PurseOfHermes => "Purse of Hermes",
+            Boon::Quicksilver => "Quicksilver",
+            Boon::WrathOfAres => "Wrath of Ares",
+            Boon::Bloodlust => "Bloodlust",
+            Boon::SpearOfAres => "Spear of Ares",
+            Boon::AegisOfAthena => "Aegis of Athena",
+            Boon::OwlSight => "Owl Sight",
+            Boon::Riposte => "Riposte",
+            Boon::CrossroadsToll => "Crossroads Toll",
+            Boon::FavourOfHecate => "Favour of Hecate",
+            Boon::TorchesOfHecate => "Torches of Hecate",
+            Boon::PactOfAres => "Pact of Ares",
+            Boon::PactOfHades => "Pact of Hades",
+            Boon::PactOfNyx => "Pact of Nyx",
+            Boon::PactOfHecate => "Pact of Hecate",
         }
     }
 
     pub fn description(self) -> &'static str {
         match self {
             Boon::VitalityOfErebus => "+10 max HP, heal to full",
-            Boon::WrathOfAres => "+3 attack",
-            Boon::AegisOfAthena => "+2 defense",
-            Boon::SwiftnessOfHermes => "All cooldowns reduced by 3",
+            Boon::HadesTithe => "Kills heal 2 HP",
+            Boon::WealthOfPlouton => "Slain enemies sometimes leave an obol",
             Boon::GraceOfPersephone => "Heal 15 HP",
+            Boon::MercyOfPersephone => "Lift all curses",
+            Boon::SpringReturns => "Each descent heals 10 more HP",
             Boon::EyesOfNyx => "See further in the dark",
+            Boon::VeilOfNyx => "+15 evasion",
+            Boon::ShadowDecoys => "Dash leaves a shadow that draws enemies for 3 turns",
+            Boon::SwiftnessOfHermes => "All cooldowns reduced by 3",
+            Boon::PurseOfHermes => "+3 obols",
+            Boon::Quicksilver => "Ability cooldowns a quarter shorter",
+            Boon::WrathOfAres => "+3 attack",
+            Boon::Bloodlust => "Kills grant +4 attack for 3 turns",
+            Boon::SpearOfAres => "+5 attack",
+            Boon::AegisOfAthena => "+2 defense",
+            Boon::OwlSight => "+10 accuracy",
+            Boon::Riposte => "Enemies that miss you in melee take 3 damage",
+            Boon::CrossroadsToll => "+3 ichor",
+            Boon::FavourOfHecate => "A random ability gains a rank",
+            Boon::TorchesOfHecate => "Your physical blows deal fire damage",
+            Boon::PactOfAres => "+5 attack, but monsters move faster",
+            Boon::PactOfHades => "+20 max HP, but descending no longer heals",
+            Boon::PactOfNyx => "+25 evasion, but you see 3 tiles less",
+            Boon::PactOfHecate => "Every ability gains a rank, but each use costs 2 HP more",
         }
     }
 
-    pub fn random_pair() -> (Boon, Boon) {
-        let all = [
-            Boon::VitalityOfErebus,
-            Boon::WrathOfAres,
-            Boon::AegisOfAthena,
-            Boon::SwiftnessOfHermes,
-            Boon::GraceOfPersephone,
-            Boon::EyesOfNyx,
-        ];
-        let mut rng = rand::thread_rng();
-        let a = rng.gen_range(0..all.len());
-        let mut b = rng.gen_range(0..all.len() - 1);
-        if b >= a { b += 1; }
-        (all[a], all[b])
+    pub fn god(self) -> God {
+        match self {
+            Boon::VitalityOfErebus | Boon::HadesTithe | Boon::WealthOfPlouton
+            | Boon::PactOfHades => God::Hades,
+            Boon::GraceOfPersephone | Boon::MercyOfPersephone | Boon::SpringReturns => God::Persephone,
+            Boon::EyesOfNyx | Boon::VeilOfNyx | Boon::ShadowDecoys | Boon::PactOfNyx => God::Nyx,
+            Boon::SwiftnessOfHermes | Boon::PurseOfHermes | Boon::Quicksilver => God::Hermes,
+            Boon::WrathOfAres | Boon::Bloodlust | Boon::SpearOfAres | Boon::PactOfAres => God::Ares,
+            Boon::AegisOfAthena | Boon::OwlSight | Boon::Riposte => God::Athena,
+            Boon::CrossroadsToll | Boon::FavourOfHecate | Boon::TorchesOfHecate
+            | Boon::PactOfHecate => God::Hecate,
+        }
+    }
+
+    pub fn rarity(self) -> Rarity {
+        match self {
+            Boon::HadesTithe | Boon::SpringReturns | Boon::VeilOfNyx | Boon::Quicksilver
+            | Boon::Bloodlust | Boon::FavourOfHecate => Rarity::Rare,
+            Boon::WealthOfPlouton | Boon::ShadowDecoys | Boon::SpearOfAres
+            | Boon::Riposte | Boon::TorchesOfHecate => Rarity::Epic,
+            Boon::PactOfAres | Boon::PactOfHades | Boon::PactOfNyx | Boon::PactOfHecate => Rarity::Bargain,
+            _ => Rarity::Common,
+        }
+    }
+
+    /// Whether taking this boon again does anything. Boons that change how
+    /// the game behaves only need taking once.
+    pub fn repeatable(self) -> bool {
+        !matches!(self, Boon::HadesTithe | Boon::WealthOfPlouton | Boon::SpringReturns
+            | Boon::ShadowDecoys | Boon::Quicksilver | Boon::Bloodlust
+            | Boon::Riposte | Boon::TorchesOfHecate | Boon::PactOfAres | Boon::PactOfHades
+            | Boon::PactOfNyx | Boon::PactOfHecate)
+    }
+
+    /// The bargain a god will strike, if it deals in them
+    pub fn pact(god: God) -> Option<Boon> {
+        match god {
+            God::Ares => Some(Boon::PactOfAres),
+            God::Hades => Some(Boon::PactOfHades),
+            God::Nyx => Some(Boon::PactOfNyx),
+            God::Hecate => Some(Boon::PactOfHecate),
+            _ => None,
+        }
+    }
+
+    /// Draw up to `count` different boons from a god's pool, weighted by rarity,
+    /// skipping one-off boons the shade already has
+    pub fn draw(god: God, count: usize, owned: &[Boon], rng: &mut impl Rng) -> Vec<Boon> {
+        let mut pool = Boon::pool(god, Rarity::Common, owned);
+        let mut drawn = Vec::new();
+        while drawn.len() < count && !pool.is_empty() {
+            let idx = pick_weighted(&pool, rng);
+            drawn.push(pool.remove(idx));
+        }
+        drawn
+    }
+
+    /// One extra boon of at least `min` rarity that isn't already on offer
+    pub fn draw_extra(god: God, min: Rarity, offered: &[Boon], owned: &[Boon], rng: &mut impl Rng) -> Option<Boon> {
+        let pool: Vec<Boon> = Boon::pool(god, min, owned).into_iter()
+            .filter(|b| !offered.contains(b))
+            .collect();
+        if pool.is_empty() {
+            return None;
+        }
+        Some(pool[pick_weighted(&pool, rng)])
+    }
+
+    fn pool(god: God, min: Rarity, owned: &[Boon]) -> Vec<Boon> {
+        Boon::ALL.iter().copied()
+            .filter(|b| b.god() == god && b.rarity() >= min && (b.repeatable() || !owned.contains(b)))
+            .collect()
+    }
+}
+
+/// Index of a boon drawn from `pool`, rarer boons less likely
+fn pick_weighted(pool: &[Boon], rng: &mut impl Rng) -> usize {
+    let total: u32 = pool.iter().map(|b| b.rarity().weight()).sum();
+    let mut roll = rng.gen_range(0..total);
+    pool.iter().position(|b| {
+        let w = b.rarity().weight();
+        if roll < w { true } else { roll -= w; false }
+    }).unwrap_or(0)
+}
+
+/// What a shade can give up at a shrine
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Offering {
+    /// Spill some HP for a third, rarer boon
+    Blood,
+    /// Give up max HP for a third boon, the rarest the god has
+    Flesh,
+    /// Leave obols for a fresh pair of boons
+    Coin,
+}
+
+impl Offering {
+    pub const ALL: [Offering; 3] = [Offering::Blood, Offering::Flesh, Offering::Coin];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Offering::Blood => "Blood",
+            Offering::Flesh => "Flesh",
+            Offering::Coin => "Coin",
+        }
+    }
+
+    /// The cost and what it buys, for the shrine menu
+    pub fn description(self) -> &'static str {
+        match self {
+            Offering::Blood => "-8 HP: a third boon, Rare or better",
+            Offering::Flesh => "-5 max HP: a third boon, Epic if the god has one",
+            Offering::Coin => "-3 obols: the god offers two new boons",
+        }
     }
 }
 
@@ -61,19 +375,27 @@ impl Boon {
 pub struct Shrine {
     pub x: i32,
     pub y: i32,
-    pub boon_a: Boon,
-    pub boon_b: Boon,
+    pub god: God,
+    pub offers: Vec<Boon>,
     pub used: bool,
+    pub offering_made: bool, // one offering per shrine
+    pub pact_struck: bool,
 }
 
 impl Shrine {
-    pub fn new(x: i32, y: i32) -> Self {
-        let (a, b) = Boon::random_pair();
+    pub fn new(x: i32, y: i32, owned: &[Boon], rng: &mut impl Rng) -> Self {
+        let god = God::random(rng);
         Shrine {
-            x, y,
-            boon_a: a,
-            boon_b: b,
+            x, y, god,
+            offers: Boon::draw(god, 2, owned, rng),
             used: false,
+            offering_made: false,
+            pact_struck: false,
         }
     }
+
+    /// Draw fresh boons from the same god
+    pub fn reroll(&mut self, owned: &[Boon], rng: &mut impl Rng) {
+        self.offers = Boon::draw(self.god, 2, owned, rng);
+    }
 }
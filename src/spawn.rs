@@ -1,10 +1,12 @@
 use rand::Rng;
+use crate::curse::{Curse, CURSE_CHANCE};
 use crate::entity::{Entity, EntityKind};
 use crate::map::{Map, Rect};
+use crate::potion::PotionEffect;
 
 /// Spawn enemies and items for a given depth
-pub fn populate_room(room: &Rect, depth: u32, entities: &mut Vec<Entity>, map: &Map) {
-    let mut rng = rand::thread_rng();
+pub fn populate_room(room: &Rect, entities: &mut Vec<Entity>, map: &Map, rng: &mut impl Rng) {
+    let depth = map.depth;
 
     // Enemies: more and harder as depth increases
     let max_enemies = match depth {
@@ -27,7 +29,7 @@ pub fn populate_room(room: &Rect, depth: u32, entities: &mut Vec<Entity>, map: &
             continue;
         }
 
-        let kind = pick_enemy(depth, &mut rng);
+        let kind = pick_enemy(depth, rng);
         entities.push(Entity::enemy(x, y, kind, depth));
     }
 
@@ -45,8 +47,12 @@ pub fn populate_room(room: &Rect, depth: u32, entities: &mut Vec<Entity>, map: &
             continue;
         }
 
-        let kind = pick_item(depth, &mut rng);
-        entities.push(Entity::item(x, y, kind));
+        let kind = pick_item(depth, rng);
+        let mut item = Entity::item(x, y, kind);
+        if kind.cursable() && rng.gen_ratio(CURSE_CHANCE, 100) {
+            item.curse = Some(Curse::random(rng));
+        }
+        entities.push(item);
     }
 }
 
@@ -79,7 +85,25 @@ fn pick_item(depth: u32, rng: &mut impl Rng) -> EntityKind {
         EntityKind::Nectar
     } else if roll < 70 {
         EntityKind::Obol
-    } else {
+    } else if roll < 82 {
         EntityKind::Moly
+    } else if depth >= 2 && roll < 90 {
+        pick_gear(rng)
+    } else if roll < 97 {
+        EntityKind::Potion(PotionEffect::random(rng))
+    } else {
+        EntityKind::SibylLeaf
     }
 }
+
+pub fn pick_gear(rng: &mut impl Rng) -> EntityKind {
+    const GEAR: [EntityKind; 6] = [
+        EntityKind::Spear,
+        EntityKind::Bident,
+        EntityKind::Sickle,
+        EntityKind::LinenShroud,
+        EntityKind::BronzeCuirass,
+        EntityKind::OwlCharm,
+    ];
+    GEAR[rng.gen_range(0..GEAR.len())]
+}
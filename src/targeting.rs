@@ -0,0 +1,42 @@
+/// What the targeting cursor is aiming for
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum TargetMode {
+    /// Throw the item in the given inventory slot at the chosen tile
+    Throw(usize),
+    /// Fire the ability in the given slot at an enemy within range
+    Ability { slot: usize, range: i32 },
+    /// Point the ability in the given slot in a direction; direction keys jump to full range
+    Direction { slot: usize, range: i32 },
+}
+
+impl TargetMode {
+    pub fn verb(self) -> &'static str {
+        match self {
+            TargetMode::Throw(_) => "Throw",
+            TargetMode::Ability { .. } => "Invoke",
+            TargetMode::Direction { .. } => "Aim",
+        }
+    }
+
+    /// Maximum distance (in tiles) the cursor may stray from the player
+    pub fn range(self) -> i32 {
+        match self {
+            TargetMode::Throw(_) => 6,
+            TargetMode::Ability { range, .. } | TargetMode::Direction { range, .. } => range,
+        }
+    }
+}
+
+/// A cursor over the map, used to aim throws (and anything else that needs a tile)
+pub struct Targeting {
+    pub mode: TargetMode,
+    pub x: i32,
+    pub y: i32,
+    pub cycle: usize, // index into the visible enemy list for Tab cycling
+}
+
+impl Targeting {
+    pub fn new(mode: TargetMode, x: i32, y: i32) -> Self {
+        Targeting { mode, x, y, cycle: 0 }
+    }
+}
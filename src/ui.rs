@@ -1,6 +1,14 @@
 use ratatui::prelude::*;
-use ratatui::widgets::{Block, Borders, Paragraph, Gauge};
+use ratatui::widgets::{Block, Borders, Clear, Paragraph, Gauge};
+use crate::ability;
 use crate::game::Game;
+use crate::equipment::Slot;
+use crate::fading::Stage;
+use crate::inventory::slot_letter;
+use crate::level;
+use crate::shop::HERMES_GLYPH;
+use crate::shrine::{Boon, Offering};
+use crate::targeting::TargetMode;
 use crate::map::{MAP_W, MAP_H, Tile};
 
 pub fn draw(frame: &mut Frame, game: &Game) {
@@ -18,14 +26,58 @@ pub fn draw(frame: &mut Frame, game: &Game) {
     draw_stats(frame, chunks[1], game);
     draw_abilities(frame, chunks[2], game);
     draw_log(frame, chunks[3], game);
+
+    if game.inventory_cursor.is_some() {
+        draw_inventory(frame, chunks[0], game);
+    }
+    if game.shop_prompt.is_some() {
+        draw_shop(frame, chunks[0], game);
+    }
+    if game.ability_menu {
+        draw_ability_menu(frame, chunks[0], game);
+    }
+    if game.shrine_prompt.is_some() {
+        draw_shrine(frame, chunks[0], game);
+    }
+    if game.character_screen {
+        draw_character(frame, chunks[0], game);
+    }
+    if !game.growth_offers.is_empty() {
+        draw_level_up(frame, chunks[0], game);
+    }
+    if !game.popups.is_empty() {
+        draw_popups(frame, chunks[0], game);
+    }
+}
+
+/// Newly earned achievements, stacked along the top of the map
+fn draw_popups(frame: &mut Frame, area: Rect, game: &Game) {
+    let gold = Color::Rgb(200, 180, 100);
+    for (i, (achievement, _)) in game.popups.iter().enumerate() {
+        let text = format!(" ★ {} — {} ", achievement.name(), achievement.description());
+        let width = (text.chars().count() as u16 + 2).min(area.width);
+        let rect = Rect::new(area.x + area.width.saturating_sub(width) / 2, area.y + 1 + i as u16 * 3, width, 3);
+        if rect.bottom() > area.bottom() {
+            break;
+        }
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(gold))
+            .title(Span::styled(" Achievement ", Style::default().fg(gold).bold()));
+        frame.render_widget(Clear, rect);
+        frame.render_widget(
+            Paragraph::new(Span::styled(text, Style::default().fg(Color::Rgb(220, 210, 180)))).block(block),
+            rect,
+        );
+    }
 }
 
 fn draw_map(frame: &mut Frame, area: Rect, game: &Game) {
     let map = &game.map;
 
     // Camera: center on player
-    let cam_x = (game.player.x as i32) - (area.width as i32 / 2);
-    let cam_y = (game.player.y as i32) - (area.height as i32 / 2);
+    let cam_x = game.player.x - (area.width as i32 / 2);
+    let cam_y = game.player.y - (area.height as i32 / 2);
 
     let buf = frame.buffer_mut();
 
@@ -52,11 +104,11 @@ fn draw_map(frame: &mut Frame, area: Rect, game: &Game) {
                         .set_fg(Color::Yellow)
                         .set_bg(Color::Black);
                 }
-                // Shrine (unused)
-                else if game.shrines.iter().any(|s| s.x == mx && s.y == my && !s.used) {
+                // Shrine (unused), in its god's colour
+                else if let Some(s) = game.shrines.iter().find(|s| s.x == mx && s.y == my && !s.used) {
                     buf[(cell_x, cell_y)]
                         .set_char('Ω')
-                        .set_fg(Color::Rgb(180, 160, 100))
+                        .set_fg(s.god.color())
                         .set_bg(Color::Black);
                 }
                 // Shrine (used)
@@ -66,11 +118,34 @@ fn draw_map(frame: &mut Frame, area: Rect, game: &Game) {
                         .set_fg(Color::DarkGray)
                         .set_bg(Color::Black);
                 }
+                // Hermes
+                else if game.shop_at(mx, my).is_some() {
+                    buf[(cell_x, cell_y)]
+                        .set_char(HERMES_GLYPH)
+                        .set_fg(Color::Rgb(220, 200, 120))
+                        .set_bg(Color::Black);
+                }
+                // Font of Lethe
+                else if let Some(font) = game.fonts.iter().find(|f| f.x == mx && f.y == my) {
+                    let fg = if font.used { Color::DarkGray } else { Color::Rgb(120, 180, 200) };
+                    buf[(cell_x, cell_y)]
+                        .set_char('≈')
+                        .set_fg(fg)
+                        .set_bg(Color::Black);
+                }
+                // Nyx's shadow decoy
+                else if game.decoy.is_some_and(|(x, y, _)| x == mx && y == my) {
+                    buf[(cell_x, cell_y)]
+                        .set_char('@')
+                        .set_fg(Color::Rgb(90, 90, 150))
+                        .set_bg(Color::Black);
+                }
                 // Entity
                 else if let Some(ent) = game.entities.iter().find(|e| e.alive && e.x == mx && e.y == my) {
+                    let fg = if ent.possessed_turns > 0 { Color::Rgb(170, 200, 230) } else { ent.kind.color() };
                     buf[(cell_x, cell_y)]
                         .set_char(ent.kind.glyph())
-                        .set_fg(ent.kind.color())
+                        .set_fg(fg)
                         .set_bg(Color::Black);
                 }
                 // Tile
@@ -92,6 +167,39 @@ fn draw_map(frame: &mut Frame, area: Rect, game: &Game) {
             }
         }
     }
+
+    // Targeting overlay: shade the line of fire, highlight the cursor
+    if let Some(t) = &game.targeting {
+        let (path, landing) = match t.mode {
+            TargetMode::Direction { range, .. } => {
+                let path = game.dash_path(t.x, t.y, range);
+                let landing = game.dash_landing(&path);
+                (path, landing)
+            }
+            _ => (game.projectile_path(game.player.x, game.player.y, t.x, t.y), None),
+        };
+        let to_screen = |mx: i32, my: i32| {
+            let sx = mx - cam_x;
+            let sy = my - cam_y;
+            if sx < 0 || sy < 0 || sx >= area.width as i32 || sy >= area.height as i32 {
+                None
+            } else {
+                Some((area.x + sx as u16, area.y + sy as u16))
+            }
+        };
+        for &(mx, my) in &path {
+            if let Some(cell) = to_screen(mx, my) {
+                buf[cell].set_bg(Color::Rgb(45, 42, 60));
+            }
+        }
+        if let Some(cell) = to_screen(t.x, t.y) {
+            buf[cell].set_bg(Color::Rgb(150, 130, 70)).set_fg(Color::Black);
+        }
+        // Where a dash would set you down
+        if let Some(cell) = landing.and_then(|(lx, ly)| to_screen(lx, ly)) {
+            buf[cell].set_char('@').set_fg(Color::Rgb(170, 200, 230)).set_bg(Color::Rgb(45, 42, 60));
+        }
+    }
 }
 
 fn tile_at(map: &crate::map::Map, x: usize, y: usize) -> Tile {
@@ -99,12 +207,13 @@ fn tile_at(map: &crate::map::Map, x: usize, y: usize) -> Tile {
 }
 
 fn tile_visible(tile: Tile) -> (char, Color) {
-    match tile {
-        Tile::Wall => ('█', Color::Rgb(50, 48, 55)),
-        Tile::Floor => ('·', Color::Rgb(70, 68, 65)),
-        Tile::Stair => ('▼', Color::Cyan),
-        Tile::Asphodel => ('✿', Color::Rgb(130, 120, 90)),
-    }
+    let fg = match tile {
+        Tile::Wall => Color::Rgb(50, 48, 55),
+        Tile::Floor => Color::Rgb(70, 68, 65),
+        Tile::Stair => Color::Cyan,
+        Tile::Asphodel => Color::Rgb(130, 120, 90),
+    };
+    (tile.glyph(), fg)
 }
 
 fn draw_stats(frame: &mut Frame, area: Rect, game: &Game) {
@@ -124,14 +233,21 @@ fn draw_stats(frame: &mut Frame, area: Rect, game: &Game) {
         .constraints([
             Constraint::Length(20), // HP
             Constraint::Length(20), // HP bar
-            Constraint::Length(15), // ATK
-            Constraint::Length(15), // DEF
-            Constraint::Length(15), // Obols
+            Constraint::Length(11), // ATK
+            Constraint::Length(10), // DEF
+            Constraint::Length(12), // Obols
+            Constraint::Length(11), // Ichor
+            Constraint::Length(16), // Level
+            Constraint::Length(11), // Fading
             Constraint::Min(0),    // status
         ])
         .split(inner);
 
-    let hp_text = format!(" HP: {}/{}", game.player.hp, game.player.max_hp);
+    let hp_text = if game.hp_cap() < game.player.max_hp {
+        format!(" HP: {}/{} (cap {})", game.player.hp, game.player.max_hp, game.hp_cap())
+    } else {
+        format!(" HP: {}/{}", game.player.hp, game.player.max_hp)
+    };
     let hp_color = if game.player.hp <= 10 { Color::Red }
         else if game.player.hp <= 20 { Color::Yellow }
         else { Color::Green };
@@ -151,9 +267,9 @@ fn draw_stats(frame: &mut Frame, area: Rect, game: &Game) {
     );
 
     let atk_str = if game.player.strength_turns > 0 {
-        format!(" ATK: {}✦", game.player.effective_attack())
+        format!(" ATK: {}✦", game.player_attack())
     } else {
-        format!(" ATK: {}", game.player.attack)
+        format!(" ATK: {}", game.player_attack())
     };
     frame.render_widget(
         Paragraph::new(atk_str).style(Style::default().fg(Color::Rgb(180, 170, 160))),
@@ -161,7 +277,7 @@ fn draw_stats(frame: &mut Frame, area: Rect, game: &Game) {
     );
 
     frame.render_widget(
-        Paragraph::new(format!(" DEF: {}", game.player.defense))
+        Paragraph::new(format!(" DEF: {}", game.player_defense()))
             .style(Style::default().fg(Color::Rgb(180, 170, 160))),
         chunks[3],
     );
@@ -172,6 +288,29 @@ fn draw_stats(frame: &mut Frame, area: Rect, game: &Game) {
         chunks[4],
     );
 
+    frame.render_widget(
+        Paragraph::new(format!(" Ichor: {}", game.ichor))
+            .style(Style::default().fg(Color::Rgb(230, 190, 60))),
+        chunks[5],
+    );
+
+    frame.render_widget(
+        Paragraph::new(format!(" Lv {}  {}/{}", game.level, game.xp, level::xp_to_next(game.level)))
+            .style(Style::default().fg(Color::Rgb(150, 170, 200))),
+        chunks[6],
+    );
+
+    let fade_color = match game.fading_stage() {
+        Stage::Whole => Color::Rgb(120, 120, 130),
+        Stage::Thinning => Color::Rgb(170, 160, 190),
+        Stage::Fraying => Color::Rgb(190, 140, 200),
+        Stage::Vanishing => Color::Rgb(220, 90, 120),
+    };
+    frame.render_widget(
+        Paragraph::new(format!(" Fade: {}", game.fading)).style(Style::default().fg(fade_color)),
+        chunks[7],
+    );
+
     // Status effects
     let mut status = Vec::new();
     if game.player.strength_turns > 0 {
@@ -180,21 +319,51 @@ fn draw_stats(frame: &mut Frame, area: Rect, game: &Game) {
     if game.blind_turns > 0 {
         status.push(format!("◌BLIND:{}", game.blind_turns));
     }
+    if game.poison_turns > 0 {
+        status.push(format!("☠POISON:{}", game.poison_turns));
+    }
+    if game.haste_turns > 0 {
+        status.push(format!("»HASTE:{}", game.haste_turns));
+    }
+    if game.wither_turns > 0 {
+        status.push(format!("†WITHER:{}", game.wither_turns));
+    }
+    if game.beacon_turns > 0 {
+        status.push(format!("†BEACON:{}", game.beacon_turns));
+    }
+    if game.phase_turns > 0 {
+        status.push(format!("░PHASE:{}", game.phase_turns));
+    }
+    if let Some((_, turns)) = game.tether {
+        status.push(format!("∞TETHER:{}", turns));
+    }
     if !status.is_empty() {
         frame.render_widget(
             Paragraph::new(format!(" {}", status.join("  ")))
                 .style(Style::default().fg(Color::Magenta)),
-            chunks[5],
+            chunks[8],
         );
     }
 }
 
 fn draw_abilities(frame: &mut Frame, area: Rect, game: &Game) {
+    if let Some(t) = &game.targeting {
+        let hint = format!(
+            " {} — [hjkl] aim  [Tab] next target  [Enter] confirm  [Esc] cancel",
+            t.mode.verb(),
+        );
+        frame.render_widget(
+            Paragraph::new(hint).style(Style::default().fg(Color::Rgb(150, 130, 70))),
+            area,
+        );
+        return;
+    }
+
     let mut spans: Vec<Span> = vec![Span::styled(" ", Style::default())];
 
-    for ab in &game.abilities {
-        let key = ab.ability.key();
-        let name = ab.ability.name();
+    for (i, ab) in game.abilities.iter().enumerate() {
+        let key = ability::key_for(i);
+        let name = format!("{} {}", ab.def.name, ability::rank_numeral(ab.rank));
 
         if ab.ready() {
             spans.push(Span::styled(
@@ -210,9 +379,296 @@ fn draw_abilities(frame: &mut Frame, area: Rect, game: &Game) {
         spans.push(Span::styled(" ", Style::default()));
     }
 
+    spans.push(Span::styled(
+        format!("[i] Pack {}/{}", game.inventory.len(), game.inventory.capacity),
+        Style::default().fg(Color::Gray),
+    ));
+
     frame.render_widget(Paragraph::new(Line::from(spans)), area);
 }
 
+/// A rectangle of the given size centred in `area`, shrunk to fit
+fn popup_rect(area: Rect, width: u16, height: u16) -> Rect {
+    let width = width.min(area.width);
+    let height = height.min(area.height);
+    Rect::new(
+        area.x + (area.width - width) / 2,
+        area.y + (area.height - height) / 2,
+        width,
+        height,
+    )
+}
+
+fn draw_shop(frame: &mut Frame, area: Rect, game: &Game) {
+    let Some(shop) = game.shop_prompt.map(|i| &game.shops[i]) else { return };
+    let rect = popup_rect(area, 68, shop.offers.len() as u16 + 4);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Rgb(220, 200, 120)))
+        .title(Span::styled(
+            format!(" {} Hermes' Stall — you have {} obols ", HERMES_GLYPH, game.obols),
+            Style::default().fg(Color::Rgb(220, 200, 120)).bold(),
+        ))
+        .title_bottom(Span::styled(
+            " [a-z] buy  [Esc] leave ",
+            Style::default().fg(Color::Rgb(120, 110, 70)),
+        ));
+    let inner = block.inner(rect);
+    frame.render_widget(Clear, rect);
+    frame.render_widget(block, rect);
+
+    let lines: Vec<Line> = shop.offers.iter().enumerate().map(|(i, offer)| {
+        let style = if offer.sold {
+            Style::default().fg(Color::Rgb(60, 58, 55))
+        } else if offer.price > game.obols {
+            Style::default().fg(Color::Rgb(120, 110, 100))
+        } else {
+            Style::default().fg(Color::Rgb(200, 190, 170))
+        };
+        let price = if offer.sold { "sold".to_string() } else { format!("{:>2} $", offer.price) };
+        Line::from(Span::styled(
+            format!(
+                " {}) {:<6} {} — {}",
+                slot_letter(i), price, game.ware_name(offer.ware), game.ware_description(offer.ware),
+            ),
+            style,
+        ))
+    }).collect();
+
+    frame.render_widget(Paragraph::new(lines), inner);
+}
+
+fn draw_ability_menu(frame: &mut Frame, area: Rect, game: &Game) {
+    let rect = popup_rect(area, 76, game.abilities.len() as u16 * 2 + 4);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Rgb(230, 190, 60)))
+        .title(Span::styled(
+            format!(" Abilities — you have {} ichor ", game.ichor),
+            Style::default().fg(Color::Rgb(230, 190, 60)).bold(),
+        ))
+        .title_bottom(Span::styled(
+            " [1-9] rank up  [Esc] close ",
+            Style::default().fg(Color::Rgb(120, 110, 70)),
+        ));
+    let inner = block.inner(rect);
+    frame.render_widget(Clear, rect);
+    frame.render_widget(block, rect);
+
+    let mut lines: Vec<Line> = Vec::new();
+    for (i, ab) in game.abilities.iter().enumerate() {
+        lines.push(Line::from(Span::styled(
+            format!(
+                " [{}] {} {} — {}",
+                ability::key_for(i), ab.def.name, ability::rank_numeral(ab.rank), ab.effect().summary(),
+            ),
+            Style::default().fg(Color::Rgb(200, 190, 170)),
+        )));
+        let next = match ab.next_effect() {
+            Some(effect) => {
+                let cost = ability::upgrade_cost(ab.rank);
+                let color = if cost <= game.ichor { Color::Rgb(230, 190, 60) } else { Color::Rgb(120, 110, 100) };
+                Span::styled(
+                    format!("     next ({} ichor): {}", cost, effect.summary()),
+                    Style::default().fg(color),
+                )
+            }
+            None => Span::styled("     highest rank", Style::default().fg(Color::Rgb(60, 58, 55))),
+        };
+        lines.push(Line::from(next));
+    }
+
+    frame.render_widget(Paragraph::new(lines), inner);
+}
+
+fn draw_shrine(frame: &mut Frame, area: Rect, game: &Game) {
+    let Some(shrine) = game.shrine_prompt.map(|i| &game.shrines[i]) else { return };
+    let rect = popup_rect(area, 76, shrine.offers.len() as u16 + 12);
+    let god = shrine.god;
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(god.color()))
+        .title(Span::styled(format!(" Ω Shrine of {} ", god.name()), Style::default().fg(god.color()).bold()))
+        .title_bottom(Span::styled(
+            " [a-c] take a boon  [1-3] offer  [p] bargain  [Esc] leave ",
+            Style::default().fg(Color::Rgb(120, 110, 70)),
+        ));
+    let inner = block.inner(rect);
+    frame.render_widget(Clear, rect);
+    frame.render_widget(block, rect);
+
+    let text = Style::default().fg(Color::Rgb(200, 190, 170));
+    let dim = Style::default().fg(Color::Rgb(60, 58, 55));
+    let heading = Style::default().fg(Color::Rgb(150, 130, 70)).bold();
+
+    let mut lines = vec![Line::from(Span::styled(" Blessings", heading))];
+    for (i, boon) in shrine.offers.iter().enumerate() {
+        lines.push(Line::from(vec![
+            Span::styled(format!(" {}) ", slot_letter(i)), text),
+            Span::styled(format!("{:<20}", boon.name()), Style::default().fg(boon.rarity().color())),
+            Span::styled(format!(" {:<7}", boon.rarity().name()), Style::default().fg(boon.rarity().color())),
+            Span::styled(format!(" {}", boon.description()), text),
+        ]));
+    }
+
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled(" Offerings (one per shrine)", heading)));
+    for (i, offering) in Offering::ALL.iter().enumerate() {
+        let style = if shrine.offering_made { dim } else { text };
+        lines.push(Line::from(Span::styled(
+            format!(" {}) {:<6} {}", i + 1, offering.name(), offering.description()),
+            style,
+        )));
+    }
+
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled(" Bargain", heading)));
+    let pact = Boon::pact(god).filter(|p| !shrine.pact_struck && !game.has_boon(*p));
+    lines.push(Line::from(match pact {
+        Some(p) => Span::styled(format!(" p) {} — {}", p.name(), p.description()), Style::default().fg(p.rarity().color())),
+        None => Span::styled(format!(" {} has no bargain for you.", god.name()), dim),
+    }));
+
+    frame.render_widget(Paragraph::new(lines), inner);
+}
+
+fn draw_level_up(frame: &mut Frame, area: Rect, game: &Game) {
+    let rect = popup_rect(area, 60, game.growth_offers.len() as u16 + 4);
+    let color = Color::Rgb(150, 170, 200);
+
+    let title = if game.levels_pending > 1 {
+        format!(" ▲ Level {} — {} choices waiting ", game.level, game.levels_pending)
+    } else {
+        format!(" ▲ Level {} ", game.level)
+    };
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(color))
+        .title(Span::styled(title, Style::default().fg(color).bold()))
+        .title_bottom(Span::styled(" [a-c] choose ", Style::default().fg(Color::Rgb(90, 100, 120))));
+    let inner = block.inner(rect);
+    frame.render_widget(Clear, rect);
+    frame.render_widget(block, rect);
+
+    let text = Style::default().fg(Color::Rgb(200, 190, 170));
+    let mut lines = vec![Line::from(Span::styled(" Something of who you were comes back.", text))];
+    for (i, growth) in game.growth_offers.iter().enumerate() {
+        lines.push(Line::from(vec![
+            Span::styled(format!(" {}) ", slot_letter(i)), text),
+            Span::styled(format!("{:<10}", growth.name()), Style::default().fg(color)),
+            Span::styled(format!(" {}", growth.description(&game.abilities)), text),
+        ]));
+    }
+
+    frame.render_widget(Paragraph::new(lines), inner);
+}
+
+fn draw_character(frame: &mut Frame, area: Rect, game: &Game) {
+    let rect = popup_rect(area, 76, game.boons.len() as u16 + 14);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Rgb(140, 130, 100)))
+        .title(Span::styled(" The Shade ", Style::default().fg(Color::Rgb(180, 170, 140)).bold()))
+        .title_bottom(Span::styled(" [Esc] close ", Style::default().fg(Color::Rgb(120, 110, 70))));
+    let inner = block.inner(rect);
+    frame.render_widget(Clear, rect);
+    frame.render_widget(block, rect);
+
+    let text = Style::default().fg(Color::Rgb(200, 190, 170));
+    let heading = Style::default().fg(Color::Rgb(150, 130, 70)).bold();
+    let p = &game.player;
+    let mut lines = vec![
+        Line::from(Span::styled(
+            format!(" Once a {}   Depth {}   Turn {}", game.background.name().to_lowercase(), game.depth, game.turns),
+            text,
+        )),
+        Line::from(Span::styled(format!(
+            " HP {}/{}   ATK {}   DEF {}   ACC {}   EVA {}   Sight {}",
+            p.hp, p.max_hp, game.player_attack(), game.player_defense(),
+            game.player_accuracy(), game.player_evasion(), game.effective_fov(),
+        ), text)),
+        Line::from(Span::styled(format!(
+            " Level {} ({}/{} XP)   Obols {}   Ichor {}   Fading {} ({})",
+            game.level, game.xp, level::xp_to_next(game.level), game.obols, game.ichor,
+            game.fading, game.fading_stage().name().to_lowercase(),
+        ), text)),
+        Line::from(""),
+        Line::from(Span::styled(" Equipped", heading)),
+    ];
+    for slot in [Slot::Weapon, Slot::Armour, Slot::Trinket] {
+        let worn = game.inventory.equipped(slot)
+            .map_or("—".to_string(), |i| game.stack_name(&game.inventory.stacks[i]));
+        lines.push(Line::from(Span::styled(format!("   {} {}", slot.marker(), worn), text)));
+    }
+    let abilities: Vec<String> = game.abilities.iter()
+        .map(|ab| format!("{} {}", ab.def.name, ability::rank_numeral(ab.rank)))
+        .collect();
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled(" Abilities", heading)));
+    lines.push(Line::from(Span::styled(format!("   {}", abilities.join(", ")), text)));
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled(format!(" Boons ({})", game.boons.len()), heading)));
+    if game.boons.is_empty() {
+        lines.push(Line::from(Span::styled("   The gods have not noticed you yet.", text)));
+    }
+    for boon in &game.boons {
+        lines.push(Line::from(vec![
+            Span::styled(format!("   {:<20}", boon.name()), Style::default().fg(boon.rarity().color())),
+            Span::styled(format!(" {:<10}", boon.god().name()), Style::default().fg(boon.god().color())),
+            Span::styled(format!(" {}", boon.description()), text),
+        ]));
+    }
+
+    frame.render_widget(Paragraph::new(lines), inner);
+}
+
+fn draw_inventory(frame: &mut Frame, area: Rect, game: &Game) {
+    let rect = popup_rect(area, 68, game.inventory.capacity as u16 + 4);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Rgb(90, 85, 70)))
+        .title(Span::styled(
+            format!(" Pack {}/{} ", game.inventory.len(), game.inventory.capacity),
+            Style::default().fg(Color::Rgb(140, 130, 100)).bold(),
+        ))
+        .title_bottom(Span::styled(
+            " [u]se [w]ield/wear [r]emove [d]rop [t]hrow [Esc] ",
+            Style::default().fg(Color::Rgb(90, 85, 70)),
+        ));
+    let inner = block.inner(rect);
+    frame.render_widget(Clear, rect);
+    frame.render_widget(block, rect);
+
+    let selected = game.inventory_cursor.unwrap_or(0);
+    let lines: Vec<Line> = game.inventory.stacks.iter().enumerate().map(|(i, stack)| {
+        let count = if stack.count > 1 { format!(" x{}", stack.count) } else { String::new() };
+        let marker = match stack.kind.equip_slot() {
+            Some(slot) if stack.equipped => format!(" {}", slot.marker()),
+            _ => String::new(),
+        };
+        let style = if i == selected {
+            Style::default().fg(Color::Black).bg(Color::Rgb(150, 130, 70))
+        } else {
+            Style::default().fg(Color::Rgb(180, 170, 160))
+        };
+        Line::from(vec![
+            Span::styled(format!(" {}) ", slot_letter(i)), style),
+            Span::styled(stack.kind.glyph().to_string(), style.fg(stack.kind.color())),
+            Span::styled(format!(
+                " {}{}{} — {}",
+                game.stack_name(stack), count, marker, game.stack_description(stack),
+            ), style),
+        ])
+    }).collect();
+
+    frame.render_widget(Paragraph::new(lines), inner);
+}
+
 fn draw_log(frame: &mut Frame, area: Rect, game: &Game) {
     let block = Block::default()
         .borders(Borders::TOP)
@@ -222,7 +678,8 @@ fn draw_log(frame: &mut Frame, area: Rect, game: &Game) {
     let inner = block.inner(area);
     frame.render_widget(block, area);
 
-    let log_lines: Vec<Line> = game.log.iter().enumerate().map(|(i, msg)| {
+    let shown = game.log.len().saturating_sub(inner.height as usize);
+    let log_lines: Vec<Line> = game.log.iter().enumerate().skip(shown).map(|(i, msg)| {
         let age = game.log.len() - 1 - i;
         let color = match age {
             0 => Color::Rgb(200, 195, 180),